@@ -0,0 +1,73 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rusty_graph::{
+    graph::{
+        djikstra::{
+            djikstra, djikstra_auto, djikstra_in, djikstra_with, workspace::SearchWorkspace,
+            DjikstraOptions, QueueMode,
+        },
+        generate::{generate_undirected_seeded, GenerationParameters},
+    },
+    heap::{pairing::PairingHeap, Heap},
+};
+
+const SEED: u64 = 2024;
+const SIZES: [i64; 3] = [100, 1_000, 10_000];
+
+fn parameters(vertex_count: i64) -> GenerationParameters {
+    GenerationParameters {
+        vertex_count,
+        neighbor_min: 2,
+        neighbor_max: 10,
+    }
+}
+
+fn shortest_paths(c: &mut Criterion) {
+    let mut group = c.benchmark_group("djikstra");
+    group.sample_size(20);
+
+    let lazy = DjikstraOptions {
+        mode: QueueMode::LazyDeletion,
+        ..DjikstraOptions::default()
+    };
+
+    for size in SIZES {
+        let graph = generate_undirected_seeded(&parameters(size), SEED).unwrap();
+        let frozen = graph.freeze();
+
+        group.bench_with_input(BenchmarkId::new("graph", size), &graph, |b, graph| {
+            b.iter(|| djikstra(graph, 0))
+        });
+
+        group.bench_with_input(BenchmarkId::new("csr", size), &frozen, |b, graph| {
+            b.iter(|| djikstra(graph, 0))
+        });
+
+        group.bench_with_input(BenchmarkId::new("csr_lazy", size), &frozen, |b, graph| {
+            b.iter(|| djikstra_with(graph, 0, Heap::new(), lazy))
+        });
+
+        group.bench_with_input(
+            BenchmarkId::new("csr_pairing", size),
+            &frozen,
+            |b, graph| {
+                b.iter(|| djikstra_with(graph, 0, PairingHeap::new(), DjikstraOptions::default()))
+            },
+        );
+
+        group.bench_with_input(BenchmarkId::new("csr_auto", size), &frozen, |b, graph| {
+            b.iter(|| djikstra_auto(graph, 0, DjikstraOptions::default()))
+        });
+
+        let mut workspace = SearchWorkspace::with_capacity(size as usize);
+        group.bench_with_input(
+            BenchmarkId::new("csr_workspace", size),
+            &frozen,
+            |b, graph| b.iter(|| djikstra_in(graph, 0, &mut workspace).unwrap()),
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, shortest_paths);
+criterion_main!(benches);
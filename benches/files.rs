@@ -0,0 +1,41 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rusty_graph::graph::{
+    generate::{generate_undirected_seeded, GenerationParameters},
+    print::print_to_file,
+    read::read_from_file,
+};
+
+const SEED: u64 = 2024;
+const SIZES: [i64; 2] = [1_000, 10_000];
+
+fn print_and_read(c: &mut Criterion) {
+    let mut group = c.benchmark_group("files");
+    group.sample_size(20);
+
+    for size in SIZES {
+        let parameters = GenerationParameters {
+            vertex_count: size,
+            neighbor_min: 2,
+            neighbor_max: 10,
+        };
+        let graph = generate_undirected_seeded(&parameters, SEED).unwrap();
+        let path = std::env::temp_dir().join(format!("rusty_graph_bench_{}.graph", size));
+        let file_name = path.to_str().unwrap();
+
+        group.bench_with_input(BenchmarkId::new("print", size), &graph, |b, graph| {
+            b.iter(|| print_to_file(graph, file_name).unwrap())
+        });
+
+        print_to_file(&graph, file_name).unwrap();
+        group.bench_with_input(BenchmarkId::new("read", size), file_name, |b, file_name| {
+            b.iter(|| read_from_file(file_name).unwrap())
+        });
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, print_and_read);
+criterion_main!(benches);
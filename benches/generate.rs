@@ -0,0 +1,29 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rusty_graph::graph::generate::{generate_undirected_seeded, GenerationParameters};
+
+const SEED: u64 = 2024;
+const SIZES: [i64; 3] = [100, 1_000, 10_000];
+
+fn generation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("generate");
+    group.sample_size(20);
+
+    for size in SIZES {
+        let parameters = GenerationParameters {
+            vertex_count: size,
+            neighbor_min: 2,
+            neighbor_max: 10,
+        };
+
+        group.bench_with_input(
+            BenchmarkId::new("undirected", size),
+            &parameters,
+            |b, parameters| b.iter(|| generate_undirected_seeded(parameters, SEED).unwrap()),
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, generation);
+criterion_main!(benches);
@@ -0,0 +1,85 @@
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use rusty_graph::heap::{dial::DialQueue, heapsort, queue::PriorityQueue, radix::RadixHeap, Heap};
+
+const SEED: u64 = 2024;
+const SIZES: [usize; 3] = [1_000, 10_000, 100_000];
+
+fn values(count: usize) -> Vec<u64> {
+    let mut rng = ChaCha8Rng::seed_from_u64(SEED);
+    Vec::from_iter((0..count).map(|_| rng.gen_range(0..1_000_000)))
+}
+
+fn equal_keys<Q: PriorityQueue<u64>>(mut queue: Q, count: usize) -> Q {
+    (0..count).for_each(|_| {
+        queue.push(1);
+    });
+    while queue.pop().is_some() {}
+    queue
+}
+
+fn insert_and_pop(c: &mut Criterion) {
+    let mut group = c.benchmark_group("heap");
+
+    for size in SIZES {
+        let data = values(size);
+
+        group.bench_with_input(BenchmarkId::new("insert", size), &data, |b, data| {
+            b.iter(|| {
+                let mut heap = Heap::new();
+                data.iter().for_each(|value| {
+                    heap.insert(*value);
+                });
+                heap
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("from_vec", size), &data, |b, data| {
+            b.iter_batched(|| data.clone(), Heap::from_vec, BatchSize::LargeInput)
+        });
+
+        group.bench_with_input(BenchmarkId::new("pop", size), &data, |b, data| {
+            b.iter_batched(
+                || Heap::from_vec(data.clone()),
+                |mut heap| while heap.pop().is_some() {},
+                BatchSize::LargeInput,
+            )
+        });
+
+        group.bench_with_input(BenchmarkId::new("decrease_key", size), &data, |b, data| {
+            b.iter_batched(
+                || {
+                    let mut heap = Heap::new();
+                    let handles = Vec::from_iter(data.iter().map(|value| heap.insert(*value)));
+                    (heap, handles)
+                },
+                |(mut heap, handles)| {
+                    handles.iter().zip(data).for_each(|(handle, value)| {
+                        heap.decrease_key(*handle, value / 2).unwrap();
+                    });
+                    heap
+                },
+                BatchSize::LargeInput,
+            )
+        });
+
+        group.bench_with_input(BenchmarkId::new("heapsort", size), &data, |b, data| {
+            b.iter_batched(|| data.clone(), heapsort, BatchSize::LargeInput)
+        });
+
+        // Every element on the same key, as in a search over a graph of equal weights.
+        group.bench_function(BenchmarkId::new("radix_equal_keys", size), |b| {
+            b.iter(|| equal_keys(RadixHeap::new(), size))
+        });
+
+        group.bench_function(BenchmarkId::new("dial_equal_keys", size), |b| {
+            b.iter(|| equal_keys(DialQueue::new(1), size))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, insert_and_pop);
+criterion_main!(benches);
@@ -0,0 +1,262 @@
+use std::{cmp::Ordering, collections::HashMap};
+
+use super::{
+    djikstra::node::Node,
+    traits::{Neighbors, Weight},
+};
+use crate::heap::Heap;
+
+/// Shortest path from `start` to the first vertex accepted by `is_goal`, as the total
+/// distance and the vertices along the way. `heuristic` estimates the remaining distance
+/// and must be consistent (never overestimate, and never drop by more than an edge's weight).
+///
+/// Only successors are queried, so this works on implicit and infinite graphs as long
+/// as a goal is reachable. Since the keys need not be ordered, ties are always broken as
+/// with [`TieBreak::InsertionOrder`](super::djikstra::TieBreak::InsertionOrder).
+pub fn a_star<G, H, P>(
+    graph: &G,
+    start: G::Key,
+    is_goal: P,
+    heuristic: H,
+) -> Option<(G::Weight, Vec<G::Key>)>
+where
+    G: Neighbors,
+    G::Weight: Weight,
+    H: Fn(&G::Key) -> G::Weight,
+    P: Fn(&G::Key) -> bool,
+{
+    let settled = best_first(graph, start, is_goal, heuristic);
+    let goal = settled.goal?;
+    Some((settled.order[goal].distance, settled.path(goal)))
+}
+
+pub(crate) struct Settled<K, W> {
+    /// Vertices in the order they were settled.
+    pub order: Vec<Node<K, W>>,
+    /// Position of the goal in `order`, if one was reached.
+    pub goal: Option<usize>,
+}
+
+impl<K: Copy + Eq + std::hash::Hash, W> Settled<K, W> {
+    fn path(&self, goal: usize) -> Vec<K> {
+        let positions: HashMap<K, usize> = self
+            .order
+            .iter()
+            .enumerate()
+            .map(|(position, node)| (node.vertex, position))
+            .collect();
+
+        let mut path = vec![self.order[goal].vertex];
+        let mut current = goal;
+        while let Some(prev) = self.order[current].prev {
+            path.push(prev);
+            current = positions[&prev];
+        }
+
+        path.reverse();
+        path
+    }
+}
+
+/// Settles vertices reachable from `start` in order of distance plus `heuristic`, stopping
+/// at the first goal. Vertices are discovered through successors only, and stale queue
+/// entries are skipped when popped instead of being decreased in place. Among equal
+/// priorities, the vertex reached first is settled first.
+pub(crate) fn best_first<G, H, P>(
+    graph: &G,
+    start: G::Key,
+    is_goal: P,
+    heuristic: H,
+) -> Settled<G::Key, G::Weight>
+where
+    G: Neighbors,
+    G::Weight: Weight,
+    H: Fn(&G::Key) -> G::Weight,
+    P: Fn(&G::Key) -> bool,
+{
+    let mut heap = Heap::new();
+    // Best distance pushed so far and the order in which each vertex was first reached.
+    let mut best = HashMap::new();
+    let mut settled = HashMap::new();
+    let mut order = Vec::new();
+
+    best.insert(start, (G::Weight::zero(), 0));
+    heap.insert(Candidate {
+        priority: heuristic(&start),
+        sequence: 0,
+        distance: G::Weight::zero(),
+        vertex: start,
+        prev: None,
+    });
+
+    while let Some(candidate) = heap.pop() {
+        if settled.contains_key(&candidate.vertex) {
+            continue;
+        }
+
+        settled.insert(candidate.vertex, order.len());
+        order.push(Node {
+            vertex: candidate.vertex,
+            distance: candidate.distance,
+            prev: candidate.prev,
+        });
+
+        if is_goal(&candidate.vertex) {
+            return Settled {
+                goal: Some(order.len() - 1),
+                order,
+            };
+        }
+
+        for (neighbor, weight) in graph.successors(&candidate.vertex) {
+            if settled.contains_key(&neighbor) {
+                continue;
+            }
+
+            let distance = candidate.distance.plus(weight);
+            let sequence = match best.get(&neighbor) {
+                Some((known, _)) if *known <= distance => continue,
+                Some((_, sequence)) => *sequence,
+                None => best.len() as u64,
+            };
+
+            best.insert(neighbor, (distance, sequence));
+            heap.insert(Candidate {
+                priority: distance.plus(heuristic(&neighbor)),
+                sequence,
+                distance,
+                vertex: neighbor,
+                prev: Some(candidate.vertex),
+            });
+        }
+    }
+
+    Settled { order, goal: None }
+}
+
+/// Queue entry ordered by `priority`, then by when its vertex was first reached, like
+/// [`Queued`](super::djikstra::node::Queued) with
+/// [`TieBreak::InsertionOrder`](super::djikstra::TieBreak::InsertionOrder).
+#[derive(Copy, Clone, Debug)]
+struct Candidate<K, W> {
+    priority: W,
+    sequence: u64,
+    distance: W,
+    vertex: K,
+    prev: Option<K>,
+}
+
+impl<K, W: Ord> PartialEq for Candidate<K, W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<K, W: Ord> Eq for Candidate<K, W> {}
+
+impl<K, W: Ord> PartialOrd for Candidate<K, W> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K, W: Ord> Ord for Candidate<K, W> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| self.sequence.cmp(&other.sequence))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{a_star, best_first};
+    use crate::graph::{djikstra::node::tuples, implicit::ImplicitGraph, Graph};
+
+    type Cell = (i32, i32);
+
+    const WALLS: [Cell; 4] = [(1, 0), (1, 1), (1, 2), (3, 4)];
+
+    fn maze() -> ImplicitGraph<Cell, u32, impl Fn(&Cell) -> Vec<(Cell, u32)>> {
+        ImplicitGraph::new(|(x, y): &Cell| {
+            [(x + 1, *y), (x - 1, *y), (*x, y + 1), (*x, y - 1)]
+                .into_iter()
+                .filter(|(x, y)| (0..5).contains(x) && (0..5).contains(y))
+                .filter(|cell| !WALLS.contains(cell))
+                .map(|cell| (cell, 1))
+                .collect()
+        })
+    }
+
+    fn manhattan(goal: Cell) -> impl Fn(&Cell) -> u32 {
+        move |(x, y)| goal.0.abs_diff(*x) + goal.1.abs_diff(*y)
+    }
+
+    #[test]
+    fn around_wall() {
+        let goal = (2, 0);
+        let (distance, path) =
+            a_star(&maze(), (0, 0), |cell| *cell == goal, manhattan(goal)).unwrap();
+
+        assert_eq!(distance, 8);
+        assert_eq!(path.len(), 9);
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert_eq!(path.last(), Some(&goal));
+        path.windows(2).for_each(|step| {
+            assert_eq!(
+                step[0].0.abs_diff(step[1].0) + step[0].1.abs_diff(step[1].1),
+                1
+            )
+        });
+    }
+
+    #[test]
+    fn settle_order() {
+        let mut graph = Graph::<i64, u64>::new();
+        (0..4).for_each(|vertex| {
+            graph.insert_vertex(vertex);
+        });
+        [(0, 1, 50), (0, 2, 30), (2, 1, 10), (1, 3, 60)]
+            .into_iter()
+            .for_each(|(from, to, weight)| {
+                graph.insert_edge(from, to, weight);
+                graph.insert_edge(to, from, weight);
+            });
+        let remaining = [100, 60, 70, 0];
+
+        let settled = best_first(
+            &graph,
+            0,
+            |vertex| *vertex == 3,
+            |vertex| remaining[*vertex as usize],
+        );
+        assert_eq!(settled.goal, Some(3));
+        assert_eq!(
+            tuples(settled.order.iter().copied()),
+            [
+                (0, 0, None),
+                (2, 30, Some(0)),
+                (1, 40, Some(2)),
+                (3, 100, Some(1))
+            ]
+        );
+        assert_eq!(settled.path(3), [0, 2, 1, 3]);
+    }
+
+    #[test]
+    fn start_is_goal() {
+        assert_eq!(
+            a_star(&maze(), (4, 4), |cell| *cell == (4, 4), |_| 0),
+            Some((0, vec![(4, 4)]))
+        );
+    }
+
+    #[test]
+    fn unreachable() {
+        let mut graph = Graph::<i64, u64>::new();
+        graph.insert_vertex(0);
+        graph.insert_vertex(1);
+
+        assert_eq!(a_star(&graph, 0, |vertex| *vertex == 1, |_| 0), None);
+    }
+}
@@ -0,0 +1,525 @@
+//! Order-insensitive comparison of graphs: equality, structural diffs and isomorphism.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{self, Debug, Display},
+    hash,
+};
+
+use super::Graph;
+
+/// Graphs are equal when they have the same vertices with the same payloads and every
+/// vertex has the same outgoing edges, counting parallel edges, in any order.
+impl<K, T, V> PartialEq for Graph<K, T, V>
+where
+    K: hash::Hash + Eq + Copy,
+    T: PartialEq,
+    V: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.content.len() == other.content.len()
+            && self.vertex_data == other.vertex_data
+            && self.content.iter().all(|(vertex, edge_list)| {
+                other.content.get(vertex).is_some_and(|other_list| {
+                    let mut unmatched = Vec::from_iter(other_list.iter());
+                    edge_list.len() == other_list.len()
+                        && edge_list.iter().all(|edge| {
+                            match unmatched.iter().position(|other_edge| edge == *other_edge) {
+                                Some(position) => {
+                                    unmatched.swap_remove(position);
+                                    true
+                                }
+                                None => false,
+                            }
+                        })
+                })
+            })
+    }
+}
+
+impl<K, T, V> Eq for Graph<K, T, V>
+where
+    K: hash::Hash + Eq + Copy,
+    T: Eq,
+    V: Eq,
+{
+}
+
+/// Changes that turn one graph into another, as produced by [`Graph::diff`].
+/// Every list is sorted by vertex key.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphDiff<K, T> {
+    pub added_vertices: Vec<K>,
+    pub removed_vertices: Vec<K>,
+    pub added_edges: Vec<(K, K, T)>,
+    pub removed_edges: Vec<(K, K, T)>,
+    /// `(from, to, old, new)` for edges whose value changed.
+    pub changed_edges: Vec<(K, K, T, T)>,
+}
+
+impl<K, T> GraphDiff<K, T> {
+    pub fn is_empty(&self) -> bool {
+        self.added_vertices.is_empty()
+            && self.removed_vertices.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+            && self.changed_edges.is_empty()
+    }
+}
+
+impl<K: Display, T: Display> Display for GraphDiff<K, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for vertex in &self.added_vertices {
+            writeln!(f, "+ vertex {}", vertex)?;
+        }
+        for vertex in &self.removed_vertices {
+            writeln!(f, "- vertex {}", vertex)?;
+        }
+        for (from, to, value) in &self.added_edges {
+            writeln!(f, "+ edge {} -> {} ({})", from, to, value)?;
+        }
+        for (from, to, value) in &self.removed_edges {
+            writeln!(f, "- edge {} -> {} ({})", from, to, value)?;
+        }
+        for (from, to, old, new) in &self.changed_edges {
+            writeln!(f, "~ edge {} -> {} ({} => {})", from, to, old, new)?;
+        }
+        Ok(())
+    }
+}
+
+impl<K, T, V> Graph<K, T, V>
+where
+    K: hash::Hash + Eq + Copy + Debug + Ord,
+    T: Debug + Clone,
+{
+    /// What has to change to get from this graph to `other`. Edges are matched by their
+    /// `(from, to)` pair; among parallel edges, equal values are paired up first and the
+    /// remaining ones count as changed, then added or removed.
+    pub fn diff(&self, other: &Graph<K, T, V>) -> GraphDiff<K, T>
+    where
+        T: PartialEq,
+    {
+        let mut diff = GraphDiff {
+            added_vertices: other
+                .vertices()
+                .filter(|vertex| !self.vertex_exists(vertex))
+                .copied()
+                .collect(),
+            removed_vertices: self
+                .vertices()
+                .filter(|vertex| !other.vertex_exists(vertex))
+                .copied()
+                .collect(),
+            added_edges: Vec::new(),
+            removed_edges: Vec::new(),
+            changed_edges: Vec::new(),
+        };
+
+        let (before, after) = (self.edges_by_pair(), other.edges_by_pair());
+        let pairs: HashSet<&(K, K)> = before.keys().chain(after.keys()).collect();
+
+        pairs.into_iter().for_each(|(from, to)| {
+            let mut removed = before.get(&(*from, *to)).cloned().unwrap_or_default();
+            let mut added = Vec::new();
+
+            after
+                .get(&(*from, *to))
+                .into_iter()
+                .flatten()
+                .for_each(|value| match removed.iter().position(|old| old == value) {
+                    Some(position) => {
+                        removed.swap_remove(position);
+                    }
+                    None => added.push(*value),
+                });
+
+            let changed = removed.len().min(added.len());
+            removed
+                .drain(..changed)
+                .zip(added.drain(..changed))
+                .for_each(|(old, new)| {
+                    diff.changed_edges
+                        .push((*from, *to, old.clone(), new.clone()))
+                });
+            removed
+                .into_iter()
+                .for_each(|value| diff.removed_edges.push((*from, *to, value.clone())));
+            added
+                .into_iter()
+                .for_each(|value| diff.added_edges.push((*from, *to, value.clone())));
+        });
+
+        diff.added_vertices.sort();
+        diff.removed_vertices.sort();
+        diff.added_edges.sort_by_key(|(from, to, _)| (*from, *to));
+        diff.removed_edges.sort_by_key(|(from, to, _)| (*from, *to));
+        diff.changed_edges
+            .sort_by_key(|(from, to, _, _)| (*from, *to));
+        diff
+    }
+
+    /// Whether the graphs have the same shape, ignoring keys, edge values and payloads.
+    pub fn is_isomorphic<L, W, U>(&self, other: &Graph<L, W, U>) -> bool
+    where
+        L: hash::Hash + Eq + Copy + Debug + Ord,
+        W: Debug + Clone,
+    {
+        self.isomorphism(other).is_some()
+    }
+
+    /// A vertex mapping onto `other` that preserves every edge, including parallel edges
+    /// and self-loops, found with a VF2-style backtracking search. Edges to missing
+    /// vertices, which graphs read from a file may have, are left out on both sides.
+    pub fn isomorphism<L, W, U>(&self, other: &Graph<L, W, U>) -> Option<HashMap<K, L>>
+    where
+        L: hash::Hash + Eq + Copy + Debug + Ord,
+        W: Debug + Clone,
+    {
+        if self.vertex_count() != other.vertex_count() {
+            return None;
+        }
+
+        let (left, right) = (Shape::of(self), Shape::of(other));
+        if left.edge_count != right.edge_count {
+            return None;
+        }
+
+        let mut matcher = Matcher {
+            order: left.matching_order(),
+            left: &left,
+            right: &right,
+            left_to_right: vec![None; left.len()],
+            right_to_left: vec![None; right.len()],
+        };
+
+        matcher.extend(0).then(|| {
+            matcher
+                .left_to_right
+                .iter()
+                .enumerate()
+                .map(|(u, v)| (left.keys[u], right.keys[v.unwrap()]))
+                .collect()
+        })
+    }
+
+    fn edges_by_pair(&self) -> HashMap<(K, K), Vec<&T>> {
+        let mut edges: HashMap<(K, K), Vec<&T>> = HashMap::new();
+        self.edges()
+            .for_each(|(from, to, value)| edges.entry((*from, *to)).or_default().push(value));
+        edges
+    }
+}
+
+/// Dense, unlabeled form of a graph used by the isomorphism search.
+struct Shape<K> {
+    keys: Vec<K>,
+    /// Distinct out- and in-neighbors of every vertex.
+    out: Vec<Vec<usize>>,
+    into: Vec<Vec<usize>>,
+    /// Number of parallel edges for every connected pair.
+    multiplicity: HashMap<(usize, usize), usize>,
+    /// Edges between existing vertices; dangling edges are not part of the shape.
+    edge_count: usize,
+}
+
+impl<K> Shape<K>
+where
+    K: hash::Hash + Eq + Copy + Debug + Ord,
+{
+    fn of<T: Debug + Clone, V>(graph: &Graph<K, T, V>) -> Shape<K> {
+        let mut keys = Vec::from_iter(graph.vertices().copied());
+        keys.sort();
+        let indices: HashMap<K, usize> = keys
+            .iter()
+            .enumerate()
+            .map(|(index, key)| (*key, index))
+            .collect();
+
+        let mut multiplicity = HashMap::new();
+        let mut edge_count = 0;
+        graph
+            .edges()
+            .filter_map(|(from, to, _)| Some((*indices.get(from)?, *indices.get(to)?)))
+            .for_each(|pair| {
+                *multiplicity.entry(pair).or_insert(0) += 1;
+                edge_count += 1;
+            });
+
+        let mut out = vec![Vec::new(); keys.len()];
+        let mut into = vec![Vec::new(); keys.len()];
+        multiplicity.keys().for_each(|(from, to)| {
+            out[*from].push(*to);
+            into[*to].push(*from);
+        });
+
+        Shape {
+            keys,
+            out,
+            into,
+            multiplicity,
+            edge_count,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    fn edges_between(&self, from: usize, to: usize) -> usize {
+        self.multiplicity.get(&(from, to)).copied().unwrap_or(0)
+    }
+
+    fn signature(&self, vertex: usize) -> (usize, usize, usize) {
+        let out_degree = self.out[vertex]
+            .iter()
+            .map(|to| self.edges_between(vertex, *to))
+            .sum();
+        let in_degree = self.into[vertex]
+            .iter()
+            .map(|from| self.edges_between(*from, vertex))
+            .sum();
+        (out_degree, in_degree, self.edges_between(vertex, vertex))
+    }
+
+    /// Visits vertices so that each one is connected to as many earlier ones as possible,
+    /// starting every component from its busiest vertex. This keeps the search pruned early.
+    fn matching_order(&self) -> Vec<usize> {
+        let mut order = Vec::with_capacity(self.len());
+        let mut placed = vec![false; self.len()];
+        let mut links = vec![0usize; self.len()];
+        let degree = |vertex: usize| self.out[vertex].len() + self.into[vertex].len();
+
+        while order.len() < self.len() {
+            let next = (0..self.len())
+                .filter(|vertex| !placed[*vertex])
+                .max_by_key(|vertex| (links[*vertex], degree(*vertex), usize::MAX - vertex))
+                .unwrap();
+
+            placed[next] = true;
+            order.push(next);
+            self.out[next]
+                .iter()
+                .chain(self.into[next].iter())
+                .for_each(|neighbor| links[*neighbor] += 1);
+        }
+
+        order
+    }
+}
+
+struct Matcher<'a, K, L> {
+    order: Vec<usize>,
+    left: &'a Shape<K>,
+    right: &'a Shape<L>,
+    left_to_right: Vec<Option<usize>>,
+    right_to_left: Vec<Option<usize>>,
+}
+
+impl<K, L> Matcher<'_, K, L>
+where
+    K: hash::Hash + Eq + Copy + Debug + Ord,
+    L: hash::Hash + Eq + Copy + Debug + Ord,
+{
+    fn extend(&mut self, depth: usize) -> bool {
+        if depth == self.order.len() {
+            return true;
+        }
+
+        let u = self.order[depth];
+        for v in 0..self.right.len() {
+            if self.right_to_left[v].is_some() || !self.feasible(u, v) {
+                continue;
+            }
+
+            self.left_to_right[u] = Some(v);
+            self.right_to_left[v] = Some(u);
+            if self.extend(depth + 1) {
+                return true;
+            }
+            self.left_to_right[u] = None;
+            self.right_to_left[v] = None;
+        }
+
+        false
+    }
+
+    /// Whether `u` can be mapped to `v`: same degrees, every edge to an already mapped
+    /// vertex has a counterpart, and both have as many neighbors still waiting to be mapped.
+    fn feasible(&self, u: usize, v: usize) -> bool {
+        if self.left.signature(u) != self.right.signature(v) {
+            return false;
+        }
+
+        let consistent = |left_neighbors: &[usize], right_neighbors: &[usize], outgoing: bool| {
+            let mut mapped = 0;
+            let mut pending = 0;
+            for neighbor in left_neighbors {
+                match self.left_to_right[*neighbor] {
+                    Some(image) => {
+                        let (expected, actual) = if outgoing {
+                            (
+                                self.left.edges_between(u, *neighbor),
+                                self.right.edges_between(v, image),
+                            )
+                        } else {
+                            (
+                                self.left.edges_between(*neighbor, u),
+                                self.right.edges_between(image, v),
+                            )
+                        };
+                        if expected != actual {
+                            return false;
+                        }
+                        mapped += 1;
+                    }
+                    None => pending += 1,
+                }
+            }
+
+            let right_mapped = right_neighbors
+                .iter()
+                .filter(|neighbor| self.right_to_left[**neighbor].is_some())
+                .count();
+            mapped == right_mapped && pending == right_neighbors.len() - right_mapped
+        };
+
+        consistent(&self.left.out[u], &self.right.out[v], true)
+            && consistent(&self.left.into[u], &self.right.into[v], false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{read::read_from_file, Graph};
+
+    fn cycle(vertices: &[i64]) -> Graph<i64, u64> {
+        let mut graph = Graph::new();
+        vertices.iter().for_each(|vertex| {
+            graph.insert_vertex(*vertex);
+        });
+        vertices
+            .iter()
+            .zip(vertices.iter().cycle().skip(1))
+            .for_each(|(from, to)| {
+                graph.insert_edge(*from, *to, 1);
+            });
+        graph
+    }
+
+    #[test]
+    fn equality_ignores_edge_order() {
+        let mut left = Graph::<i64, u64>::new();
+        let mut right = Graph::<i64, u64>::new();
+        [0, 1, 2].into_iter().for_each(|vertex| {
+            left.insert_vertex(vertex);
+            right.insert_vertex(vertex);
+        });
+        left.insert_edge(0, 1, 5);
+        left.insert_edge(0, 2, 6);
+        left.insert_edge(0, 2, 6);
+        right.insert_edge(0, 2, 6);
+        right.insert_edge(0, 1, 5);
+        right.insert_edge(0, 2, 6);
+
+        assert_ne!(left.content, right.content);
+        assert_eq!(left, right);
+
+        right.remove_edge(&0, &2);
+        right.insert_edge(0, 1, 5);
+        assert_ne!(left, right);
+    }
+
+    #[test]
+    fn diff() {
+        let mut before = Graph::<i64, u64>::new();
+        [0, 1, 2].into_iter().for_each(|vertex| {
+            before.insert_vertex(vertex);
+        });
+        before.insert_edge(0, 1, 5);
+        before.insert_edge(1, 2, 6);
+        before.insert_edge(2, 0, 7);
+
+        let mut after = Graph::<i64, u64>::new();
+        [0, 1, 3].into_iter().for_each(|vertex| {
+            after.insert_vertex(vertex);
+        });
+        after.insert_edge(0, 1, 9);
+        after.insert_edge(1, 3, 1);
+
+        let diff = before.diff(&after);
+        assert_eq!(diff.added_vertices, vec![3]);
+        assert_eq!(diff.removed_vertices, vec![2]);
+        assert_eq!(diff.added_edges, vec![(1, 3, 1)]);
+        assert_eq!(diff.removed_edges, vec![(1, 2, 6), (2, 0, 7)]);
+        assert_eq!(diff.changed_edges, vec![(0, 1, 5, 9)]);
+        assert_eq!(
+            diff.to_string(),
+            "+ vertex 3\n- vertex 2\n+ edge 1 -> 3 (1)\n- edge 1 -> 2 (6)\n- edge 2 -> 0 (7)\n~ edge 0 -> 1 (5 => 9)\n"
+        );
+
+        assert!(before.diff(&before).is_empty());
+    }
+
+    #[test]
+    fn isomorphic_cycles() {
+        let left = cycle(&[0, 1, 2, 3]);
+        let right = cycle(&[10, 30, 20, 40]);
+
+        let mapping = left.isomorphism(&right).unwrap();
+        left.edges().for_each(|(from, to, _)| {
+            assert!(right.edge_exists(&mapping[from], &mapping[to]));
+        });
+        assert!(!left.is_isomorphic(&cycle(&[0, 1, 2])));
+    }
+
+    #[test]
+    fn direction_matters() {
+        let left = cycle(&[0, 1, 2, 3]);
+
+        let mut right = Graph::<i64, u64>::new();
+        [0, 1, 2, 3].into_iter().for_each(|vertex| {
+            right.insert_vertex(vertex);
+        });
+        right.insert_edge(0, 1, 1);
+        right.insert_edge(1, 2, 1);
+        right.insert_edge(0, 3, 1);
+        right.insert_edge(3, 2, 1);
+        assert!(!left.is_isomorphic(&right));
+    }
+
+    #[test]
+    fn same_degrees_different_shape() {
+        // Two triangles versus a hexagon: every vertex has one edge in and one out.
+        let mut triangles = cycle(&[0, 1, 2]).union(&cycle(&[3, 4, 5]), |a, _| *a);
+        let hexagon = cycle(&[0, 1, 2, 3, 4, 5]);
+        assert!(!triangles.is_isomorphic(&hexagon));
+
+        triangles.remove_edge(&2, &0);
+        triangles.remove_edge(&5, &3);
+        triangles.insert_edge(2, 3, 1);
+        triangles.insert_edge(5, 0, 1);
+        assert!(triangles.is_isomorphic(&hexagon));
+    }
+
+    #[test]
+    fn dangling_edges_are_ignored() {
+        let file_name = "files/test_compare_dangling.graph";
+        std::fs::write(
+            file_name,
+            "Vertex 0:  1 1 -> 7 1 -> \nVertex 1:  2 1 -> \nVertex 2:  0 1 -> 9 1 -> \n",
+        )
+        .expect("Couldn't write graph!");
+        let graph = read_from_file(file_name).expect("Couldn't read graph!");
+        assert!(!graph.vertex_exists(&7));
+
+        assert!(graph.is_isomorphic(&cycle(&[3, 4, 5])));
+        assert!(cycle(&[3, 4, 5]).is_isomorphic(&graph));
+
+        let mut extra = cycle(&[3, 4, 5]);
+        extra.insert_edge(3, 5, 1);
+        extra.insert_edge(4, 3, 1);
+        assert_eq!(extra.edge_count(), graph.edge_count());
+        assert!(!graph.is_isomorphic(&extra));
+    }
+}
@@ -0,0 +1,247 @@
+use std::{
+    fmt::Debug,
+    hash,
+    ops::Deref,
+    panic::{self, AssertUnwindSafe},
+    sync::{Arc, Mutex, PoisonError, RwLock},
+};
+
+use super::{observe::Observers, Graph};
+
+/// A [`Graph`] shared between threads with copy-on-write snapshots.
+///
+/// Readers take a [`Snapshot`], an immutable version of the graph that stays valid for as
+/// long as they hold it, so a long query never sees a half-applied update. Writers are
+/// serialized: each [`SharedGraph::update`] clones the latest version, applies its changes
+/// and publishes the result under the next version number. Cloning costs `O(V + E)`, so
+/// group related changes into one update.
+///
+/// Observers belong to the shared graph rather than to any one version: those subscribed
+/// before [`SharedGraph::new`] or inside an update see the changes of every later update,
+/// while snapshots carry none.
+#[derive(Debug)]
+pub struct SharedGraph<K, T, V = ()> {
+    current: RwLock<Arc<Snapshot<K, T, V>>>,
+    /// Held for the whole of an update; lent to the graph being changed.
+    writer: Mutex<Observers<K, T>>,
+}
+
+/// One immutable version of a [`SharedGraph`]. Dereferences to the graph itself.
+#[derive(Debug)]
+pub struct Snapshot<K, T, V = ()> {
+    version: u64,
+    graph: Graph<K, T, V>,
+}
+
+impl<K, T, V> Snapshot<K, T, V> {
+    /// Starts at 0 for the graph the [`SharedGraph`] was created with and grows by one per update.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    pub fn graph(&self) -> &Graph<K, T, V> {
+        &self.graph
+    }
+}
+
+impl<K, T, V> Deref for Snapshot<K, T, V> {
+    type Target = Graph<K, T, V>;
+
+    fn deref(&self) -> &Graph<K, T, V> {
+        &self.graph
+    }
+}
+
+impl<K, T, V> SharedGraph<K, T, V>
+where
+    K: hash::Hash + Eq + Copy + Debug + Ord,
+    T: Debug + Clone,
+    V: Clone,
+{
+    pub fn new(mut graph: Graph<K, T, V>) -> SharedGraph<K, T, V> {
+        let observers = std::mem::take(&mut graph.observers);
+        SharedGraph {
+            current: RwLock::new(Arc::new(Snapshot { version: 0, graph })),
+            writer: Mutex::new(observers),
+        }
+    }
+
+    /// The latest published version. Never blocks on writers that are still applying changes.
+    pub fn snapshot(&self) -> Arc<Snapshot<K, T, V>> {
+        Arc::clone(&self.current.read().unwrap())
+    }
+
+    pub fn version(&self) -> u64 {
+        self.current.read().unwrap().version
+    }
+
+    /// Applies `changes` to a copy of the latest version and publishes it. Returns the new
+    /// version number together with the closure's result. The copy carries the shared
+    /// observers, so they see these changes, and subscriptions made through it last.
+    ///
+    /// If `changes` panics, nothing is published and the panic is passed on; the observers
+    /// are handed back first, so later updates keep working.
+    pub fn update<R>(&self, changes: impl FnOnce(&mut Graph<K, T, V>) -> R) -> (u64, R) {
+        let mut observers = self.writer.lock().unwrap_or_else(PoisonError::into_inner);
+
+        let latest = self.snapshot();
+        let mut graph = latest.graph.clone();
+        std::mem::swap(&mut graph.observers, &mut observers);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| changes(&mut graph)));
+        std::mem::swap(&mut graph.observers, &mut observers);
+        let result = result.unwrap_or_else(|payload| panic::resume_unwind(payload));
+        let version = latest.version + 1;
+
+        *self.current.write().unwrap() = Arc::new(Snapshot { version, graph });
+        (version, result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        panic::{self, AssertUnwindSafe},
+        sync::{Arc, Mutex},
+        thread,
+    };
+
+    use super::SharedGraph;
+    use crate::graph::{djikstra::djikstra, observe::GraphEvent, Graph};
+
+    fn line(length: i64) -> Graph<i64, u64> {
+        let mut graph = Graph::new();
+        (0..length).for_each(|vertex| {
+            graph.insert_vertex(vertex);
+        });
+        (1..length).for_each(|vertex| {
+            graph.insert_edge(vertex - 1, vertex, 1);
+            graph.insert_edge(vertex, vertex - 1, 1);
+        });
+        graph
+    }
+
+    #[test]
+    fn snapshots_are_isolated() {
+        let shared = SharedGraph::new(line(3));
+        let before = shared.snapshot();
+
+        let (version, removed) = shared.update(|graph| graph.remove_edge(&0, &1));
+        assert_eq!(version, 1);
+        assert_eq!(removed, Some(1));
+
+        assert_eq!(before.version(), 0);
+        assert!(before.edge_exists(&0, &1));
+        assert!(!shared.snapshot().edge_exists(&0, &1));
+        assert_eq!(shared.version(), 1);
+    }
+
+    #[test]
+    fn readers_during_updates() {
+        let shared = Arc::new(SharedGraph::new(line(20)));
+
+        let readers: Vec<_> = (0..4)
+            .map(|_| {
+                let shared = Arc::clone(&shared);
+                thread::spawn(move || {
+                    let mut last_version = 0;
+                    for _ in 0..50 {
+                        let snapshot = shared.snapshot();
+                        assert!(snapshot.version() >= last_version);
+                        last_version = snapshot.version();
+
+                        // Every update changes both directions of an edge at once.
+                        snapshot.edges().for_each(|(from, to, weight)| {
+                            assert_eq!(snapshot.edge_data(to, from), Some(weight));
+                        });
+                        let results = djikstra(snapshot.graph(), 0);
+                        assert_eq!(results.len(), snapshot.vertex_count());
+                    }
+                })
+            })
+            .collect();
+
+        (1..20).for_each(|vertex| {
+            shared.update(|graph| {
+                graph.set_edge_data(&(vertex - 1), &vertex, 5);
+                graph.set_edge_data(&vertex, &(vertex - 1), 5);
+            });
+        });
+
+        readers
+            .into_iter()
+            .for_each(|reader| reader.join().unwrap());
+        assert_eq!(shared.version(), 19);
+        assert_eq!(
+            djikstra(shared.snapshot().graph(), 0)
+                .last()
+                .unwrap()
+                .distance,
+            95
+        );
+    }
+
+    #[test]
+    fn observers_outlive_versions() {
+        let before = Arc::new(Mutex::new(0));
+        let during = Arc::new(Mutex::new(Vec::new()));
+
+        let mut graph = line(2);
+        let counter = Arc::clone(&before);
+        graph.subscribe(move |_| *counter.lock().unwrap() += 1);
+        let shared = SharedGraph::new(graph);
+
+        let log = Arc::clone(&during);
+        let (_, id) = shared.update(|graph| {
+            graph.insert_vertex(2);
+            graph.subscribe(move |event| {
+                if let GraphEvent::EdgeAdded { from, to, .. } = event {
+                    log.lock().unwrap().push((*from, *to));
+                }
+            })
+        });
+        shared.update(|graph| {
+            graph.insert_edge(1, 2, 1);
+        });
+        shared.update(|graph| {
+            graph.insert_edge(2, 1, 1);
+        });
+        assert_eq!(format!("{:?}", shared.snapshot().observers), "Observers(0)");
+
+        assert_eq!(*before.lock().unwrap(), 3);
+        assert_eq!(*during.lock().unwrap(), [(1, 2), (2, 1)]);
+
+        shared.update(|graph| assert!(graph.unsubscribe(id)));
+        shared.update(|graph| {
+            graph.insert_edge(0, 2, 1);
+        });
+        assert_eq!(*before.lock().unwrap(), 4);
+        assert_eq!(during.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn update_survives_panics() {
+        let events = Arc::new(Mutex::new(0));
+        let mut graph = line(2);
+        let counter = Arc::clone(&events);
+        graph.subscribe(move |_| *counter.lock().unwrap() += 1);
+        let shared = SharedGraph::new(graph);
+
+        let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+            shared.update(|graph| {
+                graph.insert_vertex(2);
+                panic!("changes failed");
+            })
+        }));
+        assert!(outcome.is_err());
+        assert_eq!(shared.version(), 0);
+        assert!(!shared.snapshot().vertex_exists(&2));
+        assert_eq!(*events.lock().unwrap(), 1);
+
+        let (version, _) = shared.update(|graph| {
+            graph.insert_vertex(3);
+        });
+        assert_eq!(version, 1);
+        assert!(shared.snapshot().vertex_exists(&3));
+        assert_eq!(*events.lock().unwrap(), 2);
+    }
+}
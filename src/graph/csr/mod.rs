@@ -0,0 +1,388 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash, iter,
+    ops::Range,
+    slice,
+};
+
+use super::{
+    traits::{EdgeWeight, GraphBase, IndexedGraph, Neighbors, VertexSet},
+    Graph,
+};
+
+/// Immutable compressed sparse row form of a [`Graph`].
+///
+/// Vertices get dense `u32` indices in key order. The outgoing edges of vertex `i`
+/// are stored in `targets[offsets[i]..offsets[i + 1]]`, with the matching weights
+/// at the same positions in `weights`.
+#[derive(Debug, Clone)]
+pub struct CsrGraph<K, T> {
+    keys: Vec<K>,
+    indices: HashMap<K, u32>,
+    offsets: Vec<u32>,
+    targets: Vec<u32>,
+    weights: Vec<T>,
+    /// Edges of the source graph that pointed at a vertex without an adjacency entry.
+    dangling: usize,
+}
+
+impl<K, T> CsrGraph<K, T>
+where
+    K: hash::Hash + Eq + Copy + Ord,
+{
+    /// Edges pointing at a vertex that has no adjacency entry of its own, as a graph read
+    /// from a file may have, are left out and counted in [`CsrGraph::dangling_edge_count`].
+    pub fn from_graph<V>(graph: &Graph<K, T, V>) -> CsrGraph<K, T>
+    where
+        T: Clone,
+    {
+        let mut keys = Vec::from_iter(graph.content.keys().copied());
+        keys.sort();
+
+        let indices: HashMap<K, u32> = keys
+            .iter()
+            .enumerate()
+            .map(|(index, key)| (*key, index as u32))
+            .collect();
+
+        let mut offsets = Vec::with_capacity(keys.len() + 1);
+        let mut targets = Vec::new();
+        let mut weights = Vec::new();
+        let mut dangling = 0;
+
+        offsets.push(0);
+        keys.iter().for_each(|key| {
+            graph.content[key]
+                .iter()
+                .for_each(|(to, weight)| match indices.get(to) {
+                    Some(index) => {
+                        targets.push(*index);
+                        weights.push(weight.clone());
+                    }
+                    None => dangling += 1,
+                });
+            offsets.push(targets.len() as u32);
+        });
+
+        CsrGraph {
+            keys,
+            indices,
+            offsets,
+            targets,
+            weights,
+            dangling,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub fn vertex_exists(&self, vertex: &K) -> bool {
+        self.indices.contains_key(vertex)
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.targets.len()
+    }
+
+    /// Number of edges dropped by [`CsrGraph::from_graph`] because their target was not a
+    /// vertex.
+    pub fn dangling_edge_count(&self) -> usize {
+        self.dangling
+    }
+
+    pub fn get_vertices(&self) -> Vec<&K> {
+        Vec::from_iter(self.vertices())
+    }
+
+    pub fn vertices(&self) -> impl Iterator<Item = &K> {
+        self.keys.iter()
+    }
+
+    /// Every edge as `(from, to, value)`, grouped by source vertex in index order.
+    pub fn edges(&self) -> impl Iterator<Item = (&K, &K, &T)> {
+        self.keys.iter().enumerate().flat_map(move |(index, from)| {
+            self.edges_of_index(index as u32)
+                .map(move |(to, value)| (from, &self.keys[to as usize], value))
+        })
+    }
+
+    pub fn edges_from(&self, vertex: &K) -> impl Iterator<Item = (&K, &T)> {
+        self.adjacency_list(vertex).into_iter().flatten()
+    }
+
+    pub fn neighbors(&self, vertex: &K) -> impl Iterator<Item = &K> {
+        self.edges_from(vertex).map(|(to, _)| to)
+    }
+
+    pub fn out_degree(&self, vertex: &K) -> usize {
+        self.index_of(vertex)
+            .map_or(0, |index| self.edge_range(index).len())
+    }
+
+    pub fn index_of(&self, vertex: &K) -> Option<u32> {
+        self.indices.get(vertex).copied()
+    }
+
+    pub fn key_of(&self, index: u32) -> Option<&K> {
+        self.keys.get(index as usize)
+    }
+
+    /// Outgoing edges of the vertex at `index` as `(target index, weight)` pairs.
+    pub fn edges_of_index(&self, index: u32) -> impl Iterator<Item = (u32, &T)> {
+        let range = self.edge_range(index);
+        self.targets[range.clone()]
+            .iter()
+            .copied()
+            .zip(self.weights[range].iter())
+    }
+
+    pub fn adjacency_list(&self, vertex: &K) -> Option<impl Iterator<Item = (&K, &T)>> {
+        let index = self.index_of(vertex)?;
+        Some(
+            self.edges_of_index(index)
+                .map(|(to, weight)| (&self.keys[to as usize], weight)),
+        )
+    }
+
+    pub fn edge_exists(&self, from: &K, to: &K) -> bool {
+        match (self.index_of(from), self.index_of(to)) {
+            (Some(from), Some(to)) => self.edges_of_index(from).any(|(target, _)| target == to),
+            _ => false,
+        }
+    }
+
+    pub fn out_neighbors(&self, vertex: &K) -> Option<HashSet<&K>> {
+        Some(self.adjacency_list(vertex)?.map(|(to, _)| to).collect())
+    }
+
+    pub fn in_neighbors(&self, vertex: &K) -> Option<HashSet<&K>> {
+        let index = self.index_of(vertex)?;
+
+        Some(
+            (0..self.keys.len() as u32)
+                .filter(|from| {
+                    *from != index && self.edges_of_index(*from).any(|(to, _)| to == index)
+                })
+                .map(|from| &self.keys[from as usize])
+                .collect(),
+        )
+    }
+}
+
+impl<K, T> CsrGraph<K, T> {
+    fn edge_range(&self, index: u32) -> Range<usize> {
+        let index = index as usize;
+        self.offsets[index] as usize..self.offsets[index + 1] as usize
+    }
+}
+
+impl<K, T> GraphBase for CsrGraph<K, T>
+where
+    K: hash::Hash + Eq + Copy,
+{
+    type Key = K;
+    type Weight = T;
+}
+
+impl<K, T> VertexSet for CsrGraph<K, T>
+where
+    K: hash::Hash + Eq + Copy,
+{
+    type VertexKeys<'a>
+        = iter::Copied<slice::Iter<'a, K>>
+    where
+        Self: 'a;
+
+    fn vertex_keys(&self) -> Self::VertexKeys<'_> {
+        self.keys.iter().copied()
+    }
+
+    fn has_vertex(&self, vertex: &K) -> bool {
+        self.indices.contains_key(vertex)
+    }
+}
+
+impl<K, T> Neighbors for CsrGraph<K, T>
+where
+    K: hash::Hash + Eq + Copy,
+    T: Clone,
+{
+    type Successors<'a>
+        = CsrSuccessors<'a, K, T>
+    where
+        Self: 'a;
+
+    fn successors(&self, vertex: &K) -> Self::Successors<'_> {
+        let range = self
+            .indices
+            .get(vertex)
+            .map_or(0..0, |index| self.edge_range(*index));
+
+        CsrSuccessors { graph: self, range }
+    }
+}
+
+impl<K, T> EdgeWeight for CsrGraph<K, T>
+where
+    K: hash::Hash + Eq + Copy,
+    T: Clone,
+{
+    fn edge_weight(&self, from: &K, to: &K) -> Option<T> {
+        let to = *self.indices.get(to)?;
+        self.edge_range(*self.indices.get(from)?)
+            .find(|position| self.targets[*position] == to)
+            .map(|position| self.weights[position].clone())
+    }
+}
+
+impl<K, T> IndexedGraph for CsrGraph<K, T>
+where
+    K: hash::Hash + Eq + Copy,
+{
+    fn index_bound(&self) -> usize {
+        self.keys.len()
+    }
+
+    fn vertex_index(&self, vertex: &K) -> Option<usize> {
+        self.indices.get(vertex).map(|index| *index as usize)
+    }
+
+    fn vertex_at(&self, index: usize) -> Option<K> {
+        self.keys.get(index).copied()
+    }
+}
+
+pub struct CsrSuccessors<'a, K, T> {
+    graph: &'a CsrGraph<K, T>,
+    range: Range<usize>,
+}
+
+impl<K: Copy, T: Clone> Iterator for CsrSuccessors<'_, K, T> {
+    type Item = (K, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let position = self.range.next()?;
+        let target = self.graph.targets[position] as usize;
+        Some((
+            self.graph.keys[target],
+            self.graph.weights[position].clone(),
+        ))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::graph::{
+        djikstra::{djikstra, node::tuples},
+        read::read_from_file,
+        traits::{EdgeWeight, IndexedGraph},
+        Graph,
+    };
+
+    fn sample() -> Graph<i64, u64> {
+        let mut graph = Graph::new();
+        graph.insert_vertex(0);
+        graph.insert_vertex(1);
+        graph.insert_vertex(2);
+        graph.insert_vertex(3);
+
+        graph.insert_edge(0, 1, 50);
+        graph.insert_edge(1, 0, 50);
+        graph.insert_edge(0, 2, 30);
+        graph.insert_edge(2, 0, 30);
+        graph.insert_edge(2, 1, 10);
+        graph.insert_edge(1, 2, 10);
+        graph.insert_edge(3, 1, 60);
+        graph.insert_edge(1, 3, 60);
+        graph
+    }
+
+    #[test]
+    fn freeze_keeps_queries() {
+        let graph = sample();
+        let frozen = graph.freeze();
+
+        assert_eq!(frozen.vertex_count(), graph.vertex_count());
+        assert_eq!(frozen.edge_count(), 8);
+        assert_eq!(frozen.edge_weight(&2, &1), graph.edge_weight(&2, &1));
+        assert_eq!(frozen.edge_weight(&3, &2), None);
+        assert!(frozen.edge_exists(&3, &1));
+        assert!(!frozen.edge_exists(&3, &2));
+        assert_eq!(frozen.out_neighbors(&1), graph.out_neighbors(&1));
+        assert_eq!(frozen.in_neighbors(&1), graph.in_neighbors(&1));
+        assert_eq!(frozen.out_neighbors(&7), None);
+        assert_eq!(frozen.out_degree(&1), graph.out_degree(&1));
+        assert_eq!(frozen.neighbors(&7).count(), 0);
+        assert_eq!(frozen.edges().count(), graph.edge_count());
+        assert_eq!(
+            HashSet::<&i64>::from_iter(frozen.get_vertices()),
+            HashSet::<&i64>::from_iter(graph.get_vertices())
+        );
+    }
+
+    #[test]
+    fn indices() {
+        let frozen = sample().freeze();
+
+        (0..frozen.vertex_count() as u32).for_each(|index| {
+            let key = frozen.key_of(index).unwrap();
+            assert_eq!(frozen.index_of(key), Some(index));
+        });
+        assert_eq!(frozen.index_of(&10), None);
+        assert_eq!(frozen.key_of(4), None);
+        assert_eq!(frozen.index_bound(), 4);
+        assert_eq!(frozen.vertex_at(4), None);
+    }
+
+    #[test]
+    fn djikstra_on_frozen() {
+        let graph = sample();
+        let frozen = graph.freeze();
+
+        assert_eq!(tuples(djikstra(&frozen, 0)), tuples(djikstra(&graph, 0)));
+        assert_eq!(
+            tuples(djikstra(&frozen, 0)),
+            [
+                (0, 0, None),
+                (2, 30, Some(0)),
+                (1, 40, Some(2)),
+                (3, 100, Some(1))
+            ]
+        );
+    }
+
+    #[test]
+    fn freeze_with_dangling_edges() {
+        let file_name = "files/test_dangling.graph";
+        std::fs::write(
+            file_name,
+            "Vertex 0:  1 20 -> 7 5 -> \nVertex 1:  9 60 -> 0 20 -> \nVertex 2: \n",
+        )
+        .expect("Couldn't write graph!");
+        let graph = read_from_file(file_name).expect("Couldn't read graph!");
+        assert!(!graph.vertex_exists(&7));
+
+        let frozen = graph.freeze();
+        assert_eq!(frozen.vertex_count(), 3);
+        assert_eq!(frozen.edge_count(), 2);
+        assert_eq!(frozen.dangling_edge_count(), 2);
+        assert!(!frozen.edge_exists(&0, &7));
+        assert_eq!(
+            Vec::from_iter(djikstra(&frozen, 0).into_iter().map(|node| node.vertex)),
+            [0, 1, 2]
+        );
+        assert_eq!(sample().freeze().dangling_edge_count(), 0);
+    }
+}
@@ -1,84 +1,386 @@
 pub mod node;
+pub mod workspace;
 
-use self::node::Node;
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+};
 
-use super::Graph;
-use crate::heap::Heap;
+use self::{
+    node::{Node, Queued},
+    workspace::{SearchWorkspace, WorkspaceError},
+};
 
-pub fn djikstra(graph: &Graph<i64, u64>, start: i64) -> Vec<Node<i64, u64>> {
-    let mut heap = Heap::new();
-    let mut answer = Vec::new();
+use super::{
+    astar::{a_star, best_first},
+    traits::{IndexedGraph, Neighbors, VertexSet, Weight},
+};
+use crate::heap::{
+    dial::DialQueue,
+    queue::{HeapError, PriorityQueue},
+    radix::{RadixHeap, RadixKey},
+    Heap,
+};
+
+/// Largest edge weight for which [`djikstra_auto`] uses a [`DialQueue`], which
+/// allocates one bucket per possible weight.
+pub const DIAL_MAX_WEIGHT: u64 = 1 << 12;
 
-    init(&mut heap, graph, start);
+/// Which of several vertices at the same distance is settled first.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum TieBreak {
+    /// Smallest vertex key first.
+    #[default]
+    VertexKey,
+    /// The vertex reached first, following the order of each vertex's successors.
+    InsertionOrder,
+}
+
+/// How a vertex that is reached again by a shorter path is updated in the queue.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum QueueMode {
+    /// Lower the queued entry in place through its handle.
+    #[default]
+    DecreaseKey,
+    /// Push another entry and skip the outdated ones when they are popped, which needs
+    /// no handles but lets the queue grow to one entry per improving edge.
+    LazyDeletion,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct DjikstraOptions {
+    pub tie_break: TieBreak,
+    pub mode: QueueMode,
+}
+
+/// Shortest distances from `start` in the order vertices are settled, breaking ties by
+/// vertex key. Unreachable vertices come last, sorted by key, with a distance of
+/// [`Weight::infinity`] and no `prev`.
+pub fn djikstra<G>(graph: &G, start: G::Key) -> Vec<Node<G::Key, G::Weight>>
+where
+    G: VertexSet + Neighbors,
+    G::Key: Ord,
+    G::Weight: Weight,
+{
+    djikstra_with(graph, start, Heap::new(), DjikstraOptions::default())
+}
 
-    loop {
-        let popped = heap.pop();
+/// Like [`djikstra`], but picks the queue from the weights: a [`DialQueue`] for unsigned
+/// weights up to [`DIAL_MAX_WEIGHT`], a [`RadixHeap`] for larger unsigned weights and the
+/// binary [`Heap`] otherwise. Finding the largest weight takes one pass over all edges.
+pub fn djikstra_auto<G>(
+    graph: &G,
+    start: G::Key,
+    options: DjikstraOptions,
+) -> Vec<Node<G::Key, G::Weight>>
+where
+    G: VertexSet + Neighbors,
+    G::Key: Ord,
+    G::Weight: Weight,
+{
+    if G::Weight::zero().to_unsigned().is_none() {
+        return djikstra_with(graph, start, Heap::new(), options);
+    }
+
+    let max_weight = graph
+        .vertex_keys()
+        .flat_map(|vertex| graph.successors(&vertex))
+        .filter_map(|(_, weight)| weight.to_unsigned())
+        .max()
+        .unwrap_or(0);
+
+    match max_weight <= DIAL_MAX_WEIGHT {
+        true => djikstra_with(graph, start, Unsigned(DialQueue::new(max_weight)), options),
+        false => djikstra_with(graph, start, Unsigned(RadixHeap::new()), options),
+    }
+}
+
+/// Hands queue entries of any weight type to an integer queue. Only built by
+/// [`djikstra_auto`] once it has seen that the weights are unsigned.
+struct Unsigned<Q>(Q);
+
+/// A queue entry together with the key [`Weight::to_unsigned`] gave its distance.
+struct Keyed<K, W> {
+    queued: Queued<K, W>,
+    key: u64,
+}
 
-        if popped.is_none() {
-            break;
+impl<K, W: Weight> Keyed<K, W> {
+    fn new(queued: Queued<K, W>) -> Self {
+        let key = queued.node.distance.to_unsigned();
+        Keyed {
+            key: key.expect("djikstra_auto checked that the weights are unsigned"),
+            queued,
         }
+    }
+}
+
+impl<K: Ord, W: Ord> PartialEq for Keyed<K, W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.queued.eq(&other.queued)
+    }
+}
+
+impl<K: Ord, W: Ord> Eq for Keyed<K, W> {}
+
+impl<K: Ord, W: Ord> PartialOrd for Keyed<K, W> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
 
-        let subject = popped.unwrap();
-
-        let neighbors = graph.adjacency_list(&subject.vertex).unwrap();
-
-        for (neighbor, neighbor_weight) in neighbors {
-            let element = heap
-                .data
-                .iter()
-                .enumerate()
-                .find(|(_, adjacent)| *neighbor == adjacent.vertex);
-
-            match element {
-                Some((index, neighbor_node))
-                    if neighbor_node.distance > neighbor_weight + subject.distance =>
-                {
-                    heap.decrease_key(
-                        index,
-                        Node {
-                            vertex: neighbor_node.vertex,
-                            distance: neighbor_weight + subject.distance,
-                            prev: Some(subject.vertex),
-                        },
-                    );
+impl<K: Ord, W: Ord> Ord for Keyed<K, W> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.queued.cmp(&other.queued)
+    }
+}
+
+impl<K, W> RadixKey for Keyed<K, W> {
+    fn radix_key(&self) -> u64 {
+        self.key
+    }
+}
+
+impl<K, W, Q> PriorityQueue<Queued<K, W>> for Unsigned<Q>
+where
+    K: Ord,
+    W: Weight,
+    Q: PriorityQueue<Keyed<K, W>>,
+{
+    type Handle = Q::Handle;
+
+    fn push(&mut self, element: Queued<K, W>) -> Q::Handle {
+        self.0.push(Keyed::new(element))
+    }
+
+    fn pop(&mut self) -> Option<Queued<K, W>> {
+        self.0.pop().map(|keyed| keyed.queued)
+    }
+
+    fn peek(&self) -> Option<&Queued<K, W>> {
+        self.0.peek().map(|keyed| &keyed.queued)
+    }
+
+    fn get(&self, handle: Q::Handle) -> Option<&Queued<K, W>> {
+        self.0.get(handle).map(|keyed| &keyed.queued)
+    }
+
+    fn decrease_key(&mut self, handle: Q::Handle, element: Queued<K, W>) -> Result<(), HeapError> {
+        self.0.decrease_key(handle, Keyed::new(element))
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+/// Like [`djikstra`], with `queue` as the priority queue and ties broken and queued
+/// vertices updated as `options` say. Both modes settle vertices in the same order.
+pub fn djikstra_with<G, Q>(
+    graph: &G,
+    start: G::Key,
+    queue: Q,
+    options: DjikstraOptions,
+) -> Vec<Node<G::Key, G::Weight>>
+where
+    G: VertexSet + Neighbors,
+    G::Key: Ord,
+    G::Weight: Weight,
+    Q: PriorityQueue<Queued<G::Key, G::Weight>>,
+{
+    if !graph.has_vertex(&start) {
+        return Vec::new();
+    }
+
+    let mut answer = match options.mode {
+        QueueMode::DecreaseKey => decrease_key(graph, start, queue, options.tie_break),
+        QueueMode::LazyDeletion => lazy_deletion(graph, start, queue, options.tie_break),
+    };
+
+    let settled = HashSet::<G::Key>::from_iter(answer.iter().map(|node| node.vertex));
+    let mut unreached = Vec::from_iter(
+        graph
+            .vertex_keys()
+            .filter(|vertex| !settled.contains(vertex)),
+    );
+    unreached.sort();
+    answer.extend(unreached.into_iter().map(|vertex| Node {
+        vertex,
+        distance: G::Weight::infinity(),
+        prev: None,
+    }));
+    answer
+}
+
+/// Settles the vertices reachable from `start`. Vertices enter the queue when first
+/// reached and are lowered in place through their handles afterwards.
+fn decrease_key<G, Q>(
+    graph: &G,
+    start: G::Key,
+    mut queue: Q,
+    tie_break: TieBreak,
+) -> Vec<Node<G::Key, G::Weight>>
+where
+    G: Neighbors,
+    G::Key: Ord,
+    G::Weight: Weight,
+    Q: PriorityQueue<Queued<G::Key, G::Weight>>,
+{
+    let mut answer = Vec::new();
+    let mut handles = HashMap::new();
+    let start_node = Node {
+        vertex: start,
+        distance: G::Weight::zero(),
+        prev: None,
+    };
+    handles.insert(start, queue.push(Queued::new(start_node, tie_break, 0)));
+
+    while let Some(Queued { node: subject, .. }) = queue.pop() {
+        for (neighbor, neighbor_weight) in graph.successors(&subject.vertex) {
+            let node = Node {
+                vertex: neighbor,
+                distance: subject.distance.plus(neighbor_weight),
+                prev: Some(subject.vertex),
+            };
+
+            match handles.get(&neighbor) {
+                // A handle whose element is gone belongs to a settled vertex.
+                Some(handle) => match queue.get(*handle) {
+                    Some(queued) if queued.node.distance > node.distance => {
+                        let rank = queued.rank;
+                        queue.decrease_key(*handle, Queued { node, rank }).unwrap();
+                    }
+                    _ => {}
+                },
+                None => {
+                    let sequence = handles.len() as u64;
+                    let queued = Queued::new(node, tie_break, sequence);
+                    handles.insert(neighbor, queue.push(queued));
                 }
-                _ => {}
             }
-        };
+        }
 
-        // println!("\n\n\n");
         answer.push(subject);
     }
+
     answer
 }
 
-fn init(heap: &mut Heap<Node<i64, u64>>, graph: &Graph<i64, u64>, start: i64) {
-    if !graph.vertex_exists(&start) {
-        return;
-    }
-
-    heap.insert(Node {
+/// Settles the vertices reachable from `start`. Every shorter path found pushes a new
+/// entry; entries for vertices that are already settled are skipped when popped.
+fn lazy_deletion<G, Q>(
+    graph: &G,
+    start: G::Key,
+    mut queue: Q,
+    tie_break: TieBreak,
+) -> Vec<Node<G::Key, G::Weight>>
+where
+    G: Neighbors,
+    G::Key: Ord,
+    G::Weight: Weight,
+    Q: PriorityQueue<Queued<G::Key, G::Weight>>,
+{
+    let mut answer = Vec::new();
+    // Best distance pushed so far and the order in which each vertex was first reached.
+    let mut best = HashMap::new();
+    let mut settled = HashSet::new();
+    let start_node = Node {
         vertex: start,
-        distance: 0,
+        distance: G::Weight::zero(),
         prev: None,
-    });
-
-    graph.content.iter().for_each(|(vertex, _)| {
-        if *vertex != start {
-            heap.insert(Node {
-                vertex: *vertex,
-                distance: u64::MAX - 1000,
-                prev: None,
-            })
+    };
+    best.insert(start, (start_node.distance, 0));
+    queue.push(Queued::new(start_node, tie_break, 0));
+
+    while let Some(Queued { node: subject, .. }) = queue.pop() {
+        if !settled.insert(subject.vertex) {
+            continue;
         }
-    });
+
+        for (neighbor, neighbor_weight) in graph.successors(&subject.vertex) {
+            if settled.contains(&neighbor) {
+                continue;
+            }
+            let node = Node {
+                vertex: neighbor,
+                distance: subject.distance.plus(neighbor_weight),
+                prev: Some(subject.vertex),
+            };
+
+            let sequence = best.len() as u64;
+            match best.get_mut(&neighbor) {
+                Some((distance, _)) if *distance <= node.distance => {}
+                Some((distance, sequence)) => {
+                    *distance = node.distance;
+                    queue.push(Queued::new(node, tie_break, *sequence));
+                }
+                None => {
+                    best.insert(neighbor, (node.distance, sequence));
+                    queue.push(Queued::new(node, tie_break, sequence));
+                }
+            }
+        }
+
+        answer.push(subject);
+    }
+
+    answer
+}
+
+/// Like [`djikstra`], but keeps every buffer in `workspace`, so searches never allocate.
+/// The results stay in the workspace until the next search; ties are broken by vertex
+/// index. Fails without searching when the graph does not fit the workspace.
+pub fn djikstra_in<G>(
+    graph: &G,
+    start: G::Key,
+    workspace: &mut SearchWorkspace<G::Weight>,
+) -> Result<(), WorkspaceError>
+where
+    G: IndexedGraph + Neighbors,
+    G::Weight: Weight,
+{
+    workspace.search(graph, start)
+}
+
+/// Like [`djikstra`], but discovers vertices through successors only, so it also runs on
+/// graphs that cannot enumerate their vertices. Only reachable vertices are returned,
+/// which means it never finishes on an infinite graph; use [`djikstra_to`] there. Ties
+/// are broken as with [`TieBreak::InsertionOrder`], which needs no order on the keys.
+pub fn djikstra_lazy<G>(graph: &G, start: G::Key) -> Vec<Node<G::Key, G::Weight>>
+where
+    G: Neighbors,
+    G::Weight: Weight,
+{
+    best_first(graph, start, |_| false, |_| G::Weight::zero()).order
+}
+
+/// Shortest path from `start` to the first vertex accepted by `is_goal`, searching lazily
+/// like [`djikstra_lazy`] and stopping as soon as a goal is settled.
+pub fn djikstra_to<G, P>(graph: &G, start: G::Key, is_goal: P) -> Option<(G::Weight, Vec<G::Key>)>
+where
+    G: Neighbors,
+    G::Weight: Weight,
+    P: Fn(&G::Key) -> bool,
+{
+    a_star(graph, start, is_goal, |_| G::Weight::zero())
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::graph::{
-        djikstra::{djikstra, Node},
-        Graph,
+
+    use crate::{
+        graph::{
+            djikstra::{
+                djikstra, djikstra_auto, djikstra_lazy, djikstra_with, node::tuples,
+                DjikstraOptions, QueueMode, TieBreak,
+            },
+            generate::{generate_undirected, GenerationParameters},
+            Graph,
+        },
+        heap::{
+            dary::DaryHeap, dial::DialQueue, fibonacci::FibonacciHeap, pairing::PairingHeap,
+            radix::RadixHeap, Heap,
+        },
     };
 
     #[test]
@@ -105,33 +407,178 @@ mod tests {
         graph.insert_edge(3, 1, 60);
         graph.insert_edge(1, 3, 60);
 
-        let results = djikstra(&graph, 0);
-        println!("{:?}", results);
-        assert_eq!(results.len(), 4);
         assert_eq!(
-            results,
-            vec![
-                Node {
-                    vertex: 0,
-                    distance: 0,
-                    prev: None
-                },
-                Node {
-                    vertex: 2,
-                    distance: 30,
-                    prev: Some(0)
-                },
-                Node {
-                    vertex: 1,
-                    distance: 40,
-                    prev: Some(2)
-                },
-                Node {
-                    vertex: 3,
-                    distance: 100,
-                    prev: Some(1)
-                }
+            tuples(djikstra(&graph, 0)),
+            [
+                (0, 0, None),
+                (2, 30, Some(0)),
+                (1, 40, Some(2)),
+                (3, 100, Some(1))
             ]
         );
     }
+
+    #[test]
+    fn queues_agree() {
+        let parameters = GenerationParameters {
+            vertex_count: 200,
+            neighbor_min: 1,
+            neighbor_max: 5,
+        };
+        let graph = generate_undirected(&parameters).unwrap();
+        let options = DjikstraOptions::default();
+        let expected = tuples(djikstra(&graph, 0));
+        assert_eq!(expected.len(), 200);
+        assert_eq!(
+            tuples(djikstra_with(&graph, 0, DaryHeap::<_, 4>::new(), options)),
+            expected
+        );
+        assert_eq!(
+            tuples(djikstra_with(&graph, 0, PairingHeap::new(), options)),
+            expected
+        );
+        assert_eq!(
+            tuples(djikstra_with(&graph, 0, FibonacciHeap::new(), options)),
+            expected
+        );
+        assert_eq!(
+            tuples(djikstra_with(&graph, 0, RadixHeap::new(), options)),
+            expected
+        );
+        assert_eq!(
+            tuples(djikstra_with(&graph, 0, DialQueue::new(99), options)),
+            expected
+        );
+        assert_eq!(tuples(djikstra_auto(&graph, 0, options)), expected);
+    }
+
+    #[test]
+    fn modes_agree() {
+        let parameters = GenerationParameters {
+            vertex_count: 300,
+            neighbor_min: 0,
+            neighbor_max: 6,
+        };
+        let graph = generate_undirected(&parameters).unwrap();
+        for tie_break in [TieBreak::VertexKey, TieBreak::InsertionOrder] {
+            let eager = DjikstraOptions {
+                tie_break,
+                mode: QueueMode::DecreaseKey,
+            };
+            let lazy = DjikstraOptions {
+                tie_break,
+                mode: QueueMode::LazyDeletion,
+            };
+
+            let expected = tuples(djikstra_with(&graph, 0, Heap::new(), eager));
+            assert_eq!(expected.len(), 300);
+            assert_eq!(
+                tuples(djikstra_with(&graph, 0, Heap::new(), lazy)),
+                expected
+            );
+            assert_eq!(
+                tuples(djikstra_with(&graph, 0, PairingHeap::new(), lazy)),
+                expected
+            );
+            assert_eq!(
+                tuples(djikstra_with(&graph, 0, DialQueue::new(99), lazy)),
+                expected
+            );
+            assert_eq!(tuples(djikstra_auto(&graph, 0, lazy)), expected);
+        }
+        assert!(djikstra_with(
+            &graph,
+            -1,
+            Heap::new(),
+            DjikstraOptions {
+                mode: QueueMode::LazyDeletion,
+                ..DjikstraOptions::default()
+            }
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn tie_break() {
+        let mut graph = Graph::new();
+        (0..8).for_each(|vertex| {
+            graph.insert_vertex(vertex);
+        });
+        [5, 3, 4, 1].into_iter().for_each(|to| {
+            graph.insert_edge(0, to, 1);
+        });
+        graph.insert_edge(1, 2, 1);
+        graph.insert_edge(3, 2, 1);
+
+        let order = |options| {
+            Vec::from_iter(
+                djikstra_with(&graph, 0, Heap::new(), options)
+                    .into_iter()
+                    .map(|node| (node.vertex, node.prev)),
+            )
+        };
+
+        assert_eq!(
+            order(DjikstraOptions::default()),
+            [
+                (0, None),
+                (1, Some(0)),
+                (3, Some(0)),
+                (4, Some(0)),
+                (5, Some(0)),
+                (2, Some(1)),
+                (6, None),
+                (7, None)
+            ]
+        );
+        // Successors come most recent edge first.
+        assert_eq!(
+            order(DjikstraOptions {
+                tie_break: TieBreak::InsertionOrder,
+                ..DjikstraOptions::default()
+            }),
+            [
+                (0, None),
+                (1, Some(0)),
+                (4, Some(0)),
+                (3, Some(0)),
+                (5, Some(0)),
+                (2, Some(1)),
+                (6, None),
+                (7, None)
+            ]
+        );
+        assert!(djikstra_lazy(&graph, 0)
+            .into_iter()
+            .map(|node| (node.vertex, node.prev))
+            .eq(order(DjikstraOptions {
+                tie_break: TieBreak::InsertionOrder,
+                ..DjikstraOptions::default()
+            })
+            .into_iter()
+            .take(6)));
+    }
+
+    #[test]
+    fn auto_with_large_and_signed_weights() {
+        let mut graph = Graph::<i64, u64>::new();
+        (0..4).for_each(|vertex| {
+            graph.insert_vertex(vertex);
+        });
+        graph.insert_edge(0, 1, 1 << 40);
+        graph.insert_edge(0, 2, 5);
+        graph.insert_edge(2, 1, 1 << 20);
+        let results = djikstra_auto(&graph, 0, DjikstraOptions::default());
+        assert_eq!(results[2].distance, (1 << 20) + 5);
+        assert_eq!(results[3].distance, u64::MAX);
+
+        let mut signed = Graph::<i64, i32>::new();
+        signed.insert_vertex(0);
+        signed.insert_vertex(1);
+        signed.insert_edge(0, 1, 3);
+        assert_eq!(
+            djikstra_auto(&signed, 0, DjikstraOptions::default())[1].distance,
+            3
+        );
+    }
 }
@@ -1,8 +1,14 @@
+use std::cmp::Ordering;
+
+use super::TieBreak;
+use crate::{graph::traits::UnsignedWeight, heap::radix::RadixKey};
+
 #[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Node<K, T> {
     pub vertex: K,
     pub distance: T,
-    pub prev: Option<K>
+    pub prev: Option<K>,
 }
 
 impl<K, T: PartialEq> PartialEq for Node<K, T> {
@@ -23,3 +29,73 @@ impl<K, T: Ord> Ord for Node<K, T> {
         self.distance.cmp(&other.distance)
     }
 }
+
+impl<K, T: UnsignedWeight> RadixKey for Node<K, T> {
+    fn radix_key(&self) -> u64 {
+        self.distance.to_u64()
+    }
+}
+
+/// Every node as a `(vertex, distance, prev)` tuple. Nodes compare by distance alone,
+/// so tests compare search results through this instead.
+#[cfg(test)]
+pub(crate) fn tuples<K, T>(nodes: impl IntoIterator<Item = Node<K, T>>) -> Vec<(K, T, Option<K>)> {
+    nodes
+        .into_iter()
+        .map(|node| (node.vertex, node.distance, node.prev))
+        .collect()
+}
+
+/// A [`Node`] waiting in the queue of [`djikstra_with`](super::djikstra_with). Ordered by
+/// distance, then by the rank its [`TieBreak`] policy gave it.
+#[derive(Copy, Clone, Debug)]
+pub struct Queued<K, T> {
+    pub node: Node<K, T>,
+    pub(crate) rank: Rank<K>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Rank<K> {
+    Key(K),
+    Sequence(u64),
+}
+
+impl<K: Copy, T> Queued<K, T> {
+    /// Entry for a vertex reached for the first time, as the `sequence`-th one.
+    pub(crate) fn new(node: Node<K, T>, tie_break: TieBreak, sequence: u64) -> Self {
+        let rank = match tie_break {
+            TieBreak::VertexKey => Rank::Key(node.vertex),
+            TieBreak::InsertionOrder => Rank::Sequence(sequence),
+        };
+        Queued { node, rank }
+    }
+}
+
+impl<K: Ord, T: Ord> PartialEq for Queued<K, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<K: Ord, T: Ord> Eq for Queued<K, T> {}
+
+impl<K: Ord, T: Ord> PartialOrd for Queued<K, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K: Ord, T: Ord> Ord for Queued<K, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.node
+            .distance
+            .cmp(&other.node.distance)
+            .then_with(|| self.rank.cmp(&other.rank))
+    }
+}
+
+impl<K, T: UnsignedWeight> RadixKey for Queued<K, T> {
+    fn radix_key(&self) -> u64 {
+        self.node.radix_key()
+    }
+}
@@ -0,0 +1,264 @@
+use super::node::Node;
+use crate::{
+    graph::traits::{IndexedGraph, Neighbors, Weight},
+    heap::{queue::Handle, Heap},
+};
+
+/// Fixed-capacity buffers for running [`djikstra_in`](super::djikstra_in) over and over.
+/// Per-vertex state is indexed by [`IndexedGraph::vertex_index`] and tagged with the
+/// search that wrote it, so starting a new search does not have to clear it. Everything
+/// is allocated up front; searches never grow the buffers and refuse graphs that do not
+/// fit.
+pub struct SearchWorkspace<W> {
+    heap: Heap<(W, usize)>,
+    distance: Vec<W>,
+    prev: Vec<Option<usize>>,
+    handles: Vec<Option<Handle>>,
+    /// Search in which each entry of the vectors above was last written.
+    stamps: Vec<u32>,
+    /// One bit per vertex settled by the current search.
+    settled: Vec<u64>,
+    /// Settled vertices, in the order they were settled.
+    order: Vec<usize>,
+    generation: u32,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum WorkspaceError {
+    /// The graph has more vertex indices than the workspace has room for.
+    TooManyVertices { index_bound: usize, capacity: usize },
+}
+
+impl<W: Weight> SearchWorkspace<W> {
+    /// Workspace for graphs with an [`IndexedGraph::index_bound`] up to `vertex_count`.
+    /// The queue holds at most one entry per vertex, so it is sized up front as well.
+    pub fn with_capacity(vertex_count: usize) -> Self {
+        let mut heap = Heap::new();
+        heap.reserve(vertex_count);
+
+        SearchWorkspace {
+            heap,
+            distance: vec![W::infinity(); vertex_count],
+            prev: vec![None; vertex_count],
+            handles: vec![None; vertex_count],
+            stamps: vec![0; vertex_count],
+            settled: vec![0; vertex_count.div_ceil(64)],
+            order: Vec::with_capacity(vertex_count),
+            generation: 0,
+        }
+    }
+
+    /// Largest [`IndexedGraph::index_bound`] this workspace can search.
+    pub fn capacity(&self) -> usize {
+        self.stamps.len()
+    }
+
+    /// Shortest distance to the vertex at `index` found by the last search, or `None` if
+    /// it was not reached.
+    pub fn distance(&self, index: usize) -> Option<W> {
+        self.is_current(index).then(|| self.distance[index])
+    }
+
+    /// Index of the vertex before the one at `index` on its shortest path.
+    pub fn prev(&self, index: usize) -> Option<usize> {
+        self.is_current(index).then(|| self.prev[index]).flatten()
+    }
+
+    /// Indices of the vertices the last search settled, in the order it settled them.
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    /// The settled vertices of the last search on `graph` as [`Node`]s, in the order
+    /// [`djikstra`](super::djikstra) returns them. Unreached vertices are left out.
+    pub fn nodes<'a, G>(&'a self, graph: &'a G) -> impl Iterator<Item = Node<G::Key, W>> + 'a
+    where
+        G: IndexedGraph<Weight = W>,
+    {
+        self.order.iter().map(move |index| Node {
+            vertex: graph.vertex_at(*index).unwrap(),
+            distance: self.distance[*index],
+            prev: self.prev[*index].map(|prev| graph.vertex_at(prev).unwrap()),
+        })
+    }
+
+    pub(super) fn search<G>(&mut self, graph: &G, start: G::Key) -> Result<(), WorkspaceError>
+    where
+        G: IndexedGraph<Weight = W> + Neighbors,
+    {
+        if graph.index_bound() > self.capacity() {
+            return Err(WorkspaceError::TooManyVertices {
+                index_bound: graph.index_bound(),
+                capacity: self.capacity(),
+            });
+        }
+
+        self.begin();
+        let Some(start_index) = graph.vertex_index(&start) else {
+            return Ok(());
+        };
+        self.reach(start_index, W::zero(), None);
+
+        while let Some((distance, index)) = self.heap.pop() {
+            self.settled[index / 64] |= 1 << (index % 64);
+            self.order.push(index);
+
+            let vertex = graph.vertex_at(index).unwrap();
+            for (neighbor, weight) in graph.successors(&vertex) {
+                let Some(neighbor) = graph.vertex_index(&neighbor) else {
+                    continue;
+                };
+                if self.is_settled(neighbor) {
+                    continue;
+                }
+
+                let candidate = distance.plus(weight);
+                if !self.is_current(neighbor) {
+                    self.reach(neighbor, candidate, Some(index));
+                } else if candidate < self.distance[neighbor] {
+                    self.distance[neighbor] = candidate;
+                    self.prev[neighbor] = Some(index);
+                    let handle = self.handles[neighbor].unwrap();
+                    self.heap
+                        .decrease_key(handle, (candidate, neighbor))
+                        .unwrap();
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Forgets the previous search. Only the settled bits are cleared one by one; every
+    /// other entry goes stale when the generation moves on.
+    fn begin(&mut self) {
+        self.order.iter().for_each(|index| {
+            self.settled[index / 64] &= !(1 << (index % 64));
+        });
+        self.order.clear();
+
+        self.generation = self.generation.wrapping_add(1);
+        if self.generation == 0 {
+            self.stamps.fill(0);
+            self.generation = 1;
+        }
+    }
+
+    fn reach(&mut self, index: usize, distance: W, prev: Option<usize>) {
+        self.stamps[index] = self.generation;
+        self.distance[index] = distance;
+        self.prev[index] = prev;
+        self.handles[index] = Some(self.heap.insert((distance, index)));
+    }
+
+    fn is_current(&self, index: usize) -> bool {
+        self.stamps.get(index) == Some(&self.generation)
+    }
+
+    fn is_settled(&self, index: usize) -> bool {
+        self.settled[index / 64] & (1 << (index % 64)) != 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SearchWorkspace, WorkspaceError};
+    use crate::graph::{
+        djikstra::{djikstra, djikstra_in, node::tuples},
+        generate::{generate_undirected_seeded, GenerationParameters},
+        traits::IndexedGraph,
+    };
+
+    #[test]
+    fn reuse_matches_djikstra() {
+        let parameters = GenerationParameters {
+            vertex_count: 200,
+            neighbor_min: 0,
+            neighbor_max: 5,
+        };
+        let small = generate_undirected_seeded(&parameters, 1).unwrap().freeze();
+        let parameters = GenerationParameters {
+            vertex_count: 500,
+            ..parameters
+        };
+        let large = generate_undirected_seeded(&parameters, 2).unwrap().freeze();
+
+        let mut workspace = SearchWorkspace::with_capacity(500);
+        for (graph, start) in [(&small, 0), (&small, 17), (&large, 3), (&small, 199)] {
+            assert_eq!(djikstra_in(graph, start, &mut workspace), Ok(()));
+
+            let expected = djikstra(graph, start);
+            let reached = expected
+                .iter()
+                .copied()
+                .take_while(|node| node.distance != u64::MAX);
+            assert_eq!(tuples(workspace.nodes(graph)), tuples(reached));
+
+            expected.iter().for_each(|node| {
+                let index = graph.vertex_index(&node.vertex).unwrap();
+                let distance = (node.distance != u64::MAX).then_some(node.distance);
+                assert_eq!(workspace.distance(index), distance);
+            });
+        }
+
+        assert_eq!(djikstra_in(&small, -1, &mut workspace), Ok(()));
+        assert!(workspace.order().is_empty());
+        assert_eq!(workspace.distance(0), None);
+    }
+
+    #[test]
+    fn fixed_capacity() {
+        let parameters = GenerationParameters {
+            vertex_count: 100,
+            neighbor_min: 1,
+            neighbor_max: 4,
+        };
+        let graph = generate_undirected_seeded(&parameters, 4).unwrap().freeze();
+
+        let mut workspace = SearchWorkspace::with_capacity(99);
+        assert_eq!(
+            djikstra_in(&graph, 0, &mut workspace),
+            Err(WorkspaceError::TooManyVertices {
+                index_bound: 100,
+                capacity: 99
+            })
+        );
+        assert!(workspace.order().is_empty());
+        assert_eq!(workspace.capacity(), 99);
+
+        let mut workspace = SearchWorkspace::with_capacity(100);
+        let capacities = |workspace: &SearchWorkspace<u64>| {
+            [
+                workspace.order.capacity(),
+                workspace.distance.capacity(),
+                workspace.stamps.capacity(),
+                workspace.settled.capacity(),
+            ]
+        };
+        let before = capacities(&workspace);
+        (0..10).for_each(|start| {
+            assert_eq!(djikstra_in(&graph, start, &mut workspace), Ok(()));
+        });
+        assert_eq!(workspace.order().len(), 100);
+        assert_eq!(capacities(&workspace), before);
+    }
+
+    #[test]
+    fn generation_wraps() {
+        let parameters = GenerationParameters {
+            vertex_count: 50,
+            neighbor_min: 1,
+            neighbor_max: 4,
+        };
+        let graph = generate_undirected_seeded(&parameters, 3).unwrap().freeze();
+
+        let mut workspace = SearchWorkspace::with_capacity(50);
+        djikstra_in(&graph, 0, &mut workspace).unwrap();
+        let first = Vec::from_iter(workspace.nodes(&graph).map(|node| node.vertex));
+
+        workspace.generation = u32::MAX;
+        djikstra_in(&graph, 0, &mut workspace).unwrap();
+        assert_eq!(workspace.generation, 1);
+        assert!(workspace.nodes(&graph).map(|node| node.vertex).eq(first));
+    }
+}
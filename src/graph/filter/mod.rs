@@ -0,0 +1,256 @@
+use super::traits::{EdgeWeight, GraphBase, IndexedGraph, Neighbors, VertexSet};
+
+/// Vertex filter of a view that only hides edges.
+pub type AllVertices<K> = fn(&K) -> bool;
+
+/// Edge filter of a view that only hides vertices.
+pub type AllEdges<K, W> = fn(&K, &K, &W) -> bool;
+
+/// Borrowed view of a graph that hides the vertices rejected by `vertex_filter`
+/// and the edges rejected by `edge_filter`. Edges touching hidden vertices are hidden too.
+pub struct Filtered<'a, G, VF, EF> {
+    graph: &'a G,
+    vertex_filter: VF,
+    edge_filter: EF,
+}
+
+impl<'a, G, VF, EF> Filtered<'a, G, VF, EF>
+where
+    G: GraphBase,
+    VF: Fn(&G::Key) -> bool,
+    EF: Fn(&G::Key, &G::Key, &G::Weight) -> bool,
+{
+    pub fn new(graph: &'a G, vertex_filter: VF, edge_filter: EF) -> Filtered<'a, G, VF, EF> {
+        Filtered {
+            graph,
+            vertex_filter,
+            edge_filter,
+        }
+    }
+
+    /// The unfiltered graph.
+    pub fn inner(&self) -> &'a G {
+        self.graph
+    }
+
+    pub fn edge_exists(&self, from: &G::Key, to: &G::Key) -> bool
+    where
+        G: Neighbors,
+    {
+        self.successors(from).any(|(neighbor, _)| neighbor == *to)
+    }
+}
+
+impl<G, VF, EF> GraphBase for Filtered<'_, G, VF, EF>
+where
+    G: GraphBase,
+{
+    type Key = G::Key;
+    type Weight = G::Weight;
+}
+
+impl<G, VF, EF> VertexSet for Filtered<'_, G, VF, EF>
+where
+    G: VertexSet,
+    VF: Fn(&G::Key) -> bool,
+{
+    type VertexKeys<'b>
+        = FilteredVertexKeys<'b, G::VertexKeys<'b>, VF>
+    where
+        Self: 'b;
+
+    fn vertex_keys(&self) -> Self::VertexKeys<'_> {
+        FilteredVertexKeys {
+            inner: self.graph.vertex_keys(),
+            vertex_filter: &self.vertex_filter,
+        }
+    }
+
+    fn has_vertex(&self, vertex: &G::Key) -> bool {
+        self.graph.has_vertex(vertex) && (self.vertex_filter)(vertex)
+    }
+}
+
+impl<G, VF, EF> Neighbors for Filtered<'_, G, VF, EF>
+where
+    G: Neighbors,
+    VF: Fn(&G::Key) -> bool,
+    EF: Fn(&G::Key, &G::Key, &G::Weight) -> bool,
+{
+    type Successors<'b>
+        = FilteredSuccessors<'b, G::Successors<'b>, G::Key, VF, EF>
+    where
+        Self: 'b;
+
+    fn successors(&self, vertex: &G::Key) -> Self::Successors<'_> {
+        FilteredSuccessors {
+            inner: self.graph.successors(vertex),
+            from: *vertex,
+            hidden: !(self.vertex_filter)(vertex),
+            vertex_filter: &self.vertex_filter,
+            edge_filter: &self.edge_filter,
+        }
+    }
+}
+
+impl<G, VF, EF> EdgeWeight for Filtered<'_, G, VF, EF>
+where
+    G: Neighbors,
+    VF: Fn(&G::Key) -> bool,
+    EF: Fn(&G::Key, &G::Key, &G::Weight) -> bool,
+{
+    fn edge_weight(&self, from: &G::Key, to: &G::Key) -> Option<G::Weight> {
+        self.successors(from)
+            .find(|(neighbor, _)| neighbor == to)
+            .map(|(_, weight)| weight)
+    }
+}
+
+/// Hidden vertices keep their index in the underlying graph but cannot be looked up.
+impl<G, VF, EF> IndexedGraph for Filtered<'_, G, VF, EF>
+where
+    G: IndexedGraph,
+    VF: Fn(&G::Key) -> bool,
+{
+    fn index_bound(&self) -> usize {
+        self.graph.index_bound()
+    }
+
+    fn vertex_index(&self, vertex: &G::Key) -> Option<usize> {
+        (self.vertex_filter)(vertex)
+            .then(|| self.graph.vertex_index(vertex))
+            .flatten()
+    }
+
+    fn vertex_at(&self, index: usize) -> Option<G::Key> {
+        self.graph
+            .vertex_at(index)
+            .filter(|vertex| (self.vertex_filter)(vertex))
+    }
+}
+
+pub struct FilteredVertexKeys<'a, I, VF> {
+    inner: I,
+    vertex_filter: &'a VF,
+}
+
+impl<K, I, VF> Iterator for FilteredVertexKeys<'_, I, VF>
+where
+    I: Iterator<Item = K>,
+    VF: Fn(&K) -> bool,
+{
+    type Item = K;
+
+    fn next(&mut self) -> Option<K> {
+        let vertex_filter = self.vertex_filter;
+        self.inner.find(|vertex| vertex_filter(vertex))
+    }
+}
+
+pub struct FilteredSuccessors<'a, I, K, VF, EF> {
+    inner: I,
+    from: K,
+    hidden: bool,
+    vertex_filter: &'a VF,
+    edge_filter: &'a EF,
+}
+
+impl<K, W, I, VF, EF> Iterator for FilteredSuccessors<'_, I, K, VF, EF>
+where
+    I: Iterator<Item = (K, W)>,
+    VF: Fn(&K) -> bool,
+    EF: Fn(&K, &K, &W) -> bool,
+{
+    type Item = (K, W);
+
+    fn next(&mut self) -> Option<(K, W)> {
+        if self.hidden {
+            return None;
+        }
+
+        let (from, vertex_filter, edge_filter) = (&self.from, self.vertex_filter, self.edge_filter);
+        self.inner
+            .find(|(to, weight)| vertex_filter(to) && edge_filter(from, to, weight))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Filtered;
+    use crate::graph::{
+        djikstra::djikstra,
+        traits::{EdgeWeight, IndexedGraph, Neighbors, VertexSet},
+        Graph,
+    };
+
+    fn roads() -> Graph<i64, u64> {
+        let mut graph = Graph::new();
+        graph.insert_vertex(0);
+        graph.insert_vertex(1);
+        graph.insert_vertex(2);
+        graph.insert_vertex(3);
+
+        graph.insert_edge(0, 1, 10);
+        graph.insert_edge(1, 3, 10);
+        graph.insert_edge(0, 2, 15);
+        graph.insert_edge(2, 3, 15);
+        graph
+    }
+
+    #[test]
+    fn hidden_vertices() {
+        let graph = roads();
+        let view = graph.filter_vertices(|vertex| *vertex != 1);
+
+        assert!(!view.has_vertex(&1));
+        assert!(view.has_vertex(&2));
+        assert_eq!(view.vertex_keys().count(), 3);
+        assert!(!view.edge_exists(&0, &1));
+        assert_eq!(view.successors(&1).count(), 0);
+        assert!(graph.edge_exists(&0, &1));
+    }
+
+    #[test]
+    fn hidden_edges() {
+        let graph = roads();
+        let view = graph.filter_edges(|from, to, _| (*from, *to) != (1, 3));
+
+        assert!(view.has_vertex(&1));
+        assert!(view.edge_exists(&0, &1));
+        assert!(!view.edge_exists(&1, &3));
+    }
+
+    #[test]
+    fn edge_weights_and_indices() {
+        let graph = roads();
+        let view = graph.filter(|vertex| *vertex != 2, |_, _, weight| *weight < 15);
+        assert_eq!(view.edge_weight(&0, &1), Some(10));
+        assert_eq!(view.edge_weight(&0, &2), None);
+
+        let frozen = graph.freeze();
+        let view = Filtered::new(&frozen, |vertex: &i64| *vertex != 2, |_, _, _| true);
+        assert_eq!(view.index_bound(), 4);
+        assert_eq!(view.vertex_index(&1), frozen.vertex_index(&1));
+        assert_eq!(view.vertex_index(&2), None);
+        assert_eq!(view.vertex_at(frozen.vertex_index(&2).unwrap()), None);
+        assert_eq!(view.edge_weight(&1, &3), Some(10));
+    }
+
+    #[test]
+    fn djikstra_around_closed_road() {
+        let graph = roads();
+        let open = djikstra(&graph, 0);
+        assert_eq!(open.last().unwrap().distance, 20);
+
+        let view = graph.filter_edges(|from, to, _| (*from, *to) != (1, 3));
+        let closed = djikstra(&view, 0);
+        let three = closed.iter().find(|node| node.vertex == 3).unwrap();
+        assert_eq!(three.distance, 30);
+        assert_eq!(three.prev, Some(2));
+
+        let view = graph.filter(|vertex| *vertex != 2, |_, _, weight| *weight < 15);
+        let results = djikstra(&view, 0);
+        assert_eq!(results.len(), 3);
+        assert_eq!(results.last().unwrap().distance, 20);
+    }
+}
@@ -1,13 +1,16 @@
 use super::Graph;
 use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GraphGenerationError {
     InvalidNeighborMin,
     InvalidNeighborMax,
     TooManyEdges,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GenerationParameters {
     pub vertex_count: i64,
     pub neighbor_min: i64,
@@ -16,6 +19,23 @@ pub struct GenerationParameters {
 
 pub fn generate_undirected(
     parameters: &GenerationParameters,
+) -> Result<Graph<i64, u64>, GraphGenerationError> {
+    generate_undirected_with(parameters, &mut rand::thread_rng())
+}
+
+/// Like [`generate_undirected`], but always generates the same graph for the same
+/// `seed` and parameters, on every platform and with every `rand` release: the seed feeds
+/// a ChaCha8 generator, whose output stream is fixed by its specification.
+pub fn generate_undirected_seeded(
+    parameters: &GenerationParameters,
+    seed: u64,
+) -> Result<Graph<i64, u64>, GraphGenerationError> {
+    generate_undirected_with(parameters, &mut ChaCha8Rng::seed_from_u64(seed))
+}
+
+pub fn generate_undirected_with<R: Rng>(
+    parameters: &GenerationParameters,
+    rng: &mut R,
 ) -> Result<Graph<i64, u64>, GraphGenerationError> {
     args_validation(parameters)?;
 
@@ -23,24 +43,22 @@ pub fn generate_undirected(
 
     let vertices = 0..parameters.vertex_count;
 
-    let mut rng = rand::thread_rng();
-
-    vertices.for_each(|vertex| fill_vertex(&mut graph, vertex, &mut rng, &parameters));
+    vertices.for_each(|vertex| fill_vertex(&mut graph, vertex, rng, parameters));
 
-    return Ok(graph);
+    Ok(graph)
 }
 
-fn fill_vertex(
+fn fill_vertex<R: Rng>(
     graph: &mut Graph<i64, u64>,
     vertex: i64,
-    rand: &mut ThreadRng,
+    rand: &mut R,
     parameters: &GenerationParameters,
-) -> () {
+) {
     if !graph.vertex_exists(&vertex) {
         graph.insert_vertex(vertex);
     }
 
-    let neighbors_count = graph.out_neighbors(&vertex).unwrap().len() as i64;
+    let neighbors_count = graph.out_degree(&vertex) as i64;
 
     let random = rand.gen_range(parameters.neighbor_min..parameters.neighbor_max);
     let random_neighbor_count = (random - neighbors_count).max(0);
@@ -56,8 +74,7 @@ fn fill_vertex(
             }
 
             let neighbor_has_space_for_neighbors =
-                (graph.adjacency_list(&random_neighbor).unwrap().len() as i64 + 1)
-                    < parameters.neighbor_max;
+                (graph.out_degree(&random_neighbor) as i64 + 1) < parameters.neighbor_max;
 
             if neighbor_has_space_for_neighbors && !graph.edge_exists(&vertex, &random_neighbor) {
                 break;
@@ -73,11 +90,11 @@ fn fill_vertex(
 fn make_sure_not_same(a: i64, vert: i64, max: i64) -> i64 {
     let mut vertex = vert;
     if a == vertex {
-        vertex = vertex + 1;
+        vertex += 1;
     }
 
     if max <= vertex {
-        vertex = vertex - 2;
+        vertex -= 2;
     }
 
     vertex
@@ -105,7 +122,9 @@ fn args_validation(args: &GenerationParameters) -> Result<(), GraphGenerationErr
 mod tests {
     use crate::graph::generate::make_sure_not_same;
 
-    use super::{generate_undirected, GenerationParameters, GraphGenerationError};
+    use super::{
+        generate_undirected, generate_undirected_seeded, GenerationParameters, GraphGenerationError,
+    };
 
     #[test]
     fn args_validation() {
@@ -173,4 +192,48 @@ mod tests {
             assert!(len >= parameters.neighbor_min);
         })
     }
+
+    #[test]
+    fn seeded() {
+        let parameters = GenerationParameters {
+            vertex_count: 100,
+            neighbor_min: 2,
+            neighbor_max: 8,
+        };
+        let edges = |seed| {
+            let graph = generate_undirected_seeded(&parameters, seed).unwrap();
+            let mut edges =
+                Vec::from_iter(graph.edges().map(|(from, to, value)| (*from, *to, *value)));
+            edges.sort();
+            edges
+        };
+
+        assert_eq!(edges(42), edges(42));
+        assert_ne!(edges(42), edges(43));
+    }
+
+    #[test]
+    fn seeded_is_pinned() {
+        let parameters = GenerationParameters {
+            vertex_count: 6,
+            neighbor_min: 0,
+            neighbor_max: 3,
+        };
+        let graph = generate_undirected_seeded(&parameters, 7).unwrap();
+        let mut edges = Vec::from_iter(graph.edges().map(|(from, to, value)| (*from, *to, *value)));
+        edges.sort();
+        assert_eq!(
+            edges,
+            vec![
+                (0, 5, 32),
+                (1, 3, 38),
+                (2, 3, 8),
+                (2, 4, 60),
+                (3, 1, 38),
+                (3, 2, 8),
+                (4, 2, 60),
+                (5, 0, 32)
+            ]
+        );
+    }
 }
@@ -0,0 +1,98 @@
+use std::{hash, marker::PhantomData};
+
+use super::traits::{GraphBase, Neighbors};
+
+/// A graph defined only by a successor function, for state spaces that are too big
+/// (or infinite) to materialize. Vertices cannot be enumerated, so use it with the
+/// lazy searches: [`djikstra_lazy`](super::djikstra::djikstra_lazy),
+/// [`djikstra_to`](super::djikstra::djikstra_to) and [`a_star`](super::astar::a_star).
+pub struct ImplicitGraph<K, T, F> {
+    successors: F,
+    marker: PhantomData<fn(&K) -> T>,
+}
+
+impl<K, T, F, I> ImplicitGraph<K, T, F>
+where
+    F: Fn(&K) -> I,
+    I: IntoIterator<Item = (K, T)>,
+{
+    pub fn new(successors: F) -> ImplicitGraph<K, T, F> {
+        ImplicitGraph {
+            successors,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<K, T, F> GraphBase for ImplicitGraph<K, T, F>
+where
+    K: hash::Hash + Eq + Copy,
+{
+    type Key = K;
+    type Weight = T;
+}
+
+impl<K, T, F, I> Neighbors for ImplicitGraph<K, T, F>
+where
+    K: hash::Hash + Eq + Copy,
+    F: Fn(&K) -> I,
+    I: IntoIterator<Item = (K, T)>,
+{
+    type Successors<'a>
+        = I::IntoIter
+    where
+        Self: 'a;
+
+    fn successors(&self, vertex: &K) -> Self::Successors<'_> {
+        (self.successors)(vertex).into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ImplicitGraph;
+    use crate::graph::{
+        djikstra::{djikstra_lazy, djikstra_to, node::tuples},
+        traits::Neighbors,
+    };
+
+    #[test]
+    fn successors() {
+        let graph = ImplicitGraph::new(|n: &u64| [(n + 1, 1u64), (n * 2, 1)]);
+        assert_eq!(
+            graph.successors(&3).collect::<Vec<_>>(),
+            vec![(4, 1), (6, 1)]
+        );
+    }
+
+    #[test]
+    fn infinite_graph() {
+        let graph = ImplicitGraph::new(|n: &u64| [(n + 1, 1u64), (n * 2, 1)]);
+
+        let (distance, path) = djikstra_to(&graph, 1, |n| *n == 10).unwrap();
+        assert_eq!(distance, 4);
+        assert_eq!(path, vec![1, 2, 4, 5, 10]);
+    }
+
+    #[test]
+    fn finite_graph() {
+        let graph = ImplicitGraph::new(|n: &i32| {
+            (*n < 3)
+                .then(|| [(n + 1, 2u32), (n + 2, 5)])
+                .into_iter()
+                .flatten()
+        });
+
+        assert_eq!(
+            tuples(djikstra_lazy(&graph, 0)),
+            [
+                (0, 0, None),
+                (1, 2, Some(0)),
+                (2, 4, Some(1)),
+                (3, 6, Some(2)),
+                (4, 9, Some(2))
+            ]
+        );
+        assert!(djikstra_to(&graph, 0, |n| *n == 7).is_none());
+    }
+}
@@ -0,0 +1,436 @@
+//! Graphs keyed by values that are not `Copy`, such as strings, tuples or UUIDs.
+//!
+//! An [`Interner`] hands out a compact [`VertexId`] for every distinct key, and a
+//! [`KeyedGraph`] stores a regular [`Graph`] over those ids, translating keys on the
+//! way in and results on the way out.
+
+use std::{borrow::Borrow, collections::HashMap, fmt::Debug, hash};
+
+use super::{
+    djikstra::{djikstra, djikstra_to, node::Node},
+    traits::Weight,
+    Graph,
+};
+
+/// Internal id of an interned key. Ids are dense, starting at 0 in interning order.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct VertexId(u32);
+
+impl VertexId {
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum InternError {
+    /// Every `u32` id is already taken.
+    TooManyKeys,
+}
+
+/// Two-way map between keys and [`VertexId`]s. Keys are never forgotten, so an id stays
+/// valid even after its vertex is removed from a graph.
+#[derive(Debug, Clone)]
+pub struct Interner<Q> {
+    keys: Vec<Q>,
+    ids: HashMap<Q, VertexId>,
+}
+
+impl<Q> Default for Interner<Q> {
+    fn default() -> Self {
+        Interner {
+            keys: Vec::new(),
+            ids: HashMap::new(),
+        }
+    }
+}
+
+impl<Q> Interner<Q>
+where
+    Q: hash::Hash + Eq + Clone,
+{
+    pub fn new() -> Interner<Q> {
+        Interner::default()
+    }
+
+    /// Id of `key`, assigning the next free one if it was not seen before.
+    ///
+    /// # Panics
+    ///
+    /// If `key` is new and every id is taken; see [`Interner::try_intern`].
+    pub fn intern(&mut self, key: Q) -> VertexId {
+        self.try_intern(key)
+            .expect("no vertex ids left to intern a new key")
+    }
+
+    /// Like [`Interner::intern`], but reports running out of ids instead of panicking.
+    pub fn try_intern(&mut self, key: Q) -> Result<VertexId, InternError> {
+        if let Some(id) = self.ids.get(&key) {
+            return Ok(*id);
+        }
+
+        let id = next_id(self.keys.len())?;
+        self.keys.push(key.clone());
+        self.ids.insert(key, id);
+        Ok(id)
+    }
+
+    pub fn id<B>(&self, key: &B) -> Option<VertexId>
+    where
+        Q: Borrow<B>,
+        B: hash::Hash + Eq + ?Sized,
+    {
+        self.ids.get(key).copied()
+    }
+
+    pub fn resolve(&self, id: VertexId) -> Option<&Q> {
+        self.keys.get(id.index())
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Every key with its id, in id order.
+    pub fn iter(&self) -> impl Iterator<Item = (VertexId, &Q)> {
+        self.keys
+            .iter()
+            .enumerate()
+            .map(|(index, key)| (VertexId(index as u32), key))
+    }
+}
+
+/// Id for the key interned after `len` others.
+fn next_id(len: usize) -> Result<VertexId, InternError> {
+    u32::try_from(len)
+        .map(VertexId)
+        .map_err(|_| InternError::TooManyKeys)
+}
+
+/// A [`Graph`] over interned keys of type `Q`.
+#[derive(Debug, Clone)]
+pub struct KeyedGraph<Q, T, V = ()> {
+    graph: Graph<VertexId, T, V>,
+    interner: Interner<Q>,
+}
+
+impl<Q, T> KeyedGraph<Q, T>
+where
+    Q: hash::Hash + Eq + Clone,
+    T: Debug + Clone,
+{
+    pub fn new() -> KeyedGraph<Q, T> {
+        KeyedGraph::with_vertex_data()
+    }
+}
+
+impl<Q, T, V> Default for KeyedGraph<Q, T, V>
+where
+    Q: hash::Hash + Eq + Clone,
+    T: Debug + Clone,
+{
+    fn default() -> Self {
+        KeyedGraph::with_vertex_data()
+    }
+}
+
+impl<Q, T, V> KeyedGraph<Q, T, V>
+where
+    Q: hash::Hash + Eq + Clone,
+    T: Debug + Clone,
+{
+    pub fn with_vertex_data() -> KeyedGraph<Q, T, V> {
+        KeyedGraph {
+            graph: Graph::with_vertex_data(),
+            interner: Interner::new(),
+        }
+    }
+
+    /// The underlying graph, for running algorithms on ids directly.
+    pub fn graph(&self) -> &Graph<VertexId, T, V> {
+        &self.graph
+    }
+
+    pub fn interner(&self) -> &Interner<Q> {
+        &self.interner
+    }
+
+    pub fn id<B>(&self, key: &B) -> Option<VertexId>
+    where
+        Q: Borrow<B>,
+        B: hash::Hash + Eq + ?Sized,
+    {
+        self.interner.id(key)
+    }
+
+    pub fn key(&self, id: VertexId) -> Option<&Q> {
+        self.interner.resolve(id)
+    }
+
+    /// Adds `vertex` unless it already exists, and returns its id. Unlike
+    /// [`Graph::insert_vertex`], existing edges are kept.
+    ///
+    /// # Panics
+    ///
+    /// If `vertex` is new and its interner has run out of ids.
+    pub fn insert_vertex(&mut self, vertex: Q) -> VertexId {
+        self.try_insert_vertex(vertex)
+            .expect("no vertex ids left to intern a new key")
+    }
+
+    /// Like [`KeyedGraph::insert_vertex`], but reports running out of ids instead of panicking.
+    pub fn try_insert_vertex(&mut self, vertex: Q) -> Result<VertexId, InternError> {
+        let id = self.interner.try_intern(vertex)?;
+        if !self.graph.vertex_exists(&id) {
+            self.graph.insert_vertex(id);
+        }
+        Ok(id)
+    }
+
+    pub fn insert_vertex_with_data(&mut self, vertex: Q, data: V) -> VertexId {
+        let id = self.insert_vertex(vertex);
+        self.graph.set_vertex_data(&id, data);
+        id
+    }
+
+    pub fn remove_vertex<B>(&mut self, vertex: &B) -> bool
+    where
+        Q: Borrow<B>,
+        B: hash::Hash + Eq + ?Sized,
+    {
+        self.id(vertex)
+            .and_then(|id| self.graph.remove_vertex(&id))
+            .is_some()
+    }
+
+    pub fn vertex_exists<B>(&self, vertex: &B) -> bool
+    where
+        Q: Borrow<B>,
+        B: hash::Hash + Eq + ?Sized,
+    {
+        self.id(vertex)
+            .is_some_and(|id| self.graph.vertex_exists(&id))
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        self.graph.vertex_count()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.graph.edge_count()
+    }
+
+    pub fn vertex_data<B>(&self, vertex: &B) -> Option<&V>
+    where
+        Q: Borrow<B>,
+        B: hash::Hash + Eq + ?Sized,
+    {
+        self.graph.vertex_data(&self.id(vertex)?)
+    }
+
+    pub fn vertices(&self) -> impl Iterator<Item = &Q> {
+        self.graph
+            .vertices()
+            .map(|id| &self.interner.keys[id.index()])
+    }
+
+    /// Every edge as `(from, to, value)`.
+    pub fn edges(&self) -> impl Iterator<Item = (&Q, &Q, &T)> {
+        self.graph.edges().map(|(from, to, value)| {
+            (
+                &self.interner.keys[from.index()],
+                &self.interner.keys[to.index()],
+                value,
+            )
+        })
+    }
+
+    pub fn edges_from<B>(&self, vertex: &B) -> impl Iterator<Item = (&Q, &T)>
+    where
+        Q: Borrow<B>,
+        B: hash::Hash + Eq + ?Sized,
+    {
+        self.id(vertex)
+            .and_then(|id| self.graph.adjacency_list(&id))
+            .into_iter()
+            .flatten()
+            .map(|(to, value)| (&self.interner.keys[to.index()], value))
+    }
+
+    pub fn insert_edge<B>(&mut self, from: &B, to: &B, value: T) -> Option<()>
+    where
+        Q: Borrow<B>,
+        B: hash::Hash + Eq + ?Sized,
+    {
+        let (from, to) = (self.id(from)?, self.id(to)?);
+        self.graph.insert_edge(from, to, value)
+    }
+
+    pub fn edge_data<B>(&self, from: &B, to: &B) -> Option<&T>
+    where
+        Q: Borrow<B>,
+        B: hash::Hash + Eq + ?Sized,
+    {
+        self.graph.edge_data(&self.id(from)?, &self.id(to)?)
+    }
+
+    pub fn set_edge_data<B>(&mut self, from: &B, to: &B, value: T) -> Option<T>
+    where
+        Q: Borrow<B>,
+        B: hash::Hash + Eq + ?Sized,
+    {
+        let (from, to) = (self.id(from)?, self.id(to)?);
+        self.graph.set_edge_data(&from, &to, value)
+    }
+
+    pub fn remove_edge<B>(&mut self, from: &B, to: &B) -> Option<T>
+    where
+        Q: Borrow<B>,
+        B: hash::Hash + Eq + ?Sized,
+    {
+        let (from, to) = (self.id(from)?, self.id(to)?);
+        self.graph.remove_edge(&from, &to)
+    }
+
+    /// [`djikstra`] from `start`, reported in terms of the original keys. Empty when
+    /// `start` is not a vertex.
+    pub fn djikstra<B>(&self, start: &B) -> Vec<Node<&Q, T>>
+    where
+        Q: Borrow<B>,
+        B: hash::Hash + Eq + ?Sized,
+        T: Weight,
+    {
+        let Some(start) = self.id(start) else {
+            return Vec::new();
+        };
+
+        djikstra(&self.graph, start)
+            .into_iter()
+            .map(|node| Node {
+                vertex: &self.interner.keys[node.vertex.index()],
+                distance: node.distance,
+                prev: node.prev.map(|prev| &self.interner.keys[prev.index()]),
+            })
+            .collect()
+    }
+
+    /// Shortest path from `from` to `to` as the total distance and the keys along the way.
+    pub fn shortest_path<B>(&self, from: &B, to: &B) -> Option<(T, Vec<&Q>)>
+    where
+        Q: Borrow<B>,
+        B: hash::Hash + Eq + ?Sized,
+        T: Weight,
+    {
+        let (from, to) = (self.id(from)?, self.id(to)?);
+        if !self.graph.vertex_exists(&from) {
+            return None;
+        }
+
+        let (distance, path) = djikstra_to(&self.graph, from, |vertex| *vertex == to)?;
+        Some((
+            distance,
+            path.into_iter()
+                .map(|id| &self.interner.keys[id.index()])
+                .collect(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{next_id, InternError, Interner, KeyedGraph, VertexId};
+
+    fn cities() -> KeyedGraph<String, u64> {
+        let mut graph = KeyedGraph::new();
+        ["Vilnius", "Kaunas", "Klaipeda", "Siauliai"]
+            .into_iter()
+            .for_each(|city| {
+                graph.insert_vertex(city.to_owned());
+            });
+
+        [
+            ("Vilnius", "Kaunas", 100),
+            ("Kaunas", "Klaipeda", 210),
+            ("Vilnius", "Siauliai", 215),
+            ("Siauliai", "Klaipeda", 160),
+        ]
+        .into_iter()
+        .for_each(|(from, to, distance)| {
+            graph.insert_edge(from, to, distance);
+            graph.insert_edge(to, from, distance);
+        });
+        graph
+    }
+
+    #[test]
+    fn interner() {
+        let mut interner = Interner::new();
+        let a = interner.intern((1, "a"));
+        let b = interner.intern((2, "b"));
+
+        assert_eq!(interner.intern((1, "a")), a);
+        assert_ne!(a, b);
+        assert_eq!(interner.resolve(b), Some(&(2, "b")));
+        assert_eq!(interner.id(&(3, "c")), None);
+        assert_eq!(interner.len(), 2);
+    }
+
+    #[test]
+    fn keyed_graph() {
+        let mut graph = cities();
+
+        assert_eq!(graph.vertex_count(), 4);
+        assert_eq!(graph.edge_count(), 8);
+        assert_eq!(graph.edge_data("Kaunas", "Klaipeda"), Some(&210));
+        assert_eq!(graph.insert_edge("Kaunas", "Riga", 260), None);
+        assert_eq!(graph.edges_from("Siauliai").count(), 2);
+
+        let id = graph.insert_vertex("Vilnius".to_owned());
+        assert_eq!(graph.key(id).map(String::as_str), Some("Vilnius"));
+        assert_eq!(graph.edges_from("Vilnius").count(), 2);
+
+        assert!(graph.remove_vertex("Siauliai"));
+        assert!(!graph.vertex_exists("Siauliai"));
+        assert_eq!(graph.edge_count(), 4);
+    }
+
+    #[test]
+    fn results_use_keys() {
+        let graph = cities();
+
+        let order = Vec::from_iter(
+            graph
+                .djikstra("Vilnius")
+                .into_iter()
+                .map(|node| (node.vertex.as_str(), node.distance)),
+        );
+        assert_eq!(
+            order,
+            [
+                ("Vilnius", 0),
+                ("Kaunas", 100),
+                ("Siauliai", 215),
+                ("Klaipeda", 310)
+            ]
+        );
+        assert_eq!(graph.djikstra("Riga"), Vec::new());
+
+        let (distance, path) = graph.shortest_path("Klaipeda", "Vilnius").unwrap();
+        assert_eq!(distance, 310);
+        assert_eq!(path, ["Klaipeda", "Kaunas", "Vilnius"]);
+    }
+
+    #[test]
+    fn id_overflow() {
+        assert_eq!(next_id(0), Ok(VertexId(0)));
+        assert_eq!(next_id(u32::MAX as usize), Ok(VertexId(u32::MAX)));
+        assert_eq!(
+            next_id(u32::MAX as usize + 1),
+            Err(InternError::TooManyKeys)
+        );
+    }
+}
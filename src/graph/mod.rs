@@ -1,23 +1,112 @@
+pub mod astar;
+pub mod compare;
+pub mod concurrent;
+pub mod csr;
 pub mod djikstra;
+pub mod filter;
 pub mod generate;
-pub mod read;
+pub mod implicit;
+pub mod intern;
+pub mod observe;
+pub mod ops;
 pub mod print;
+pub mod read;
+#[cfg(feature = "serde")]
+pub mod serialize;
+pub mod stats;
+pub mod traits;
+pub mod transaction;
+pub mod transpose;
+
+use std::{
+    collections::{hash_map, HashMap, HashSet, LinkedList},
+    fmt::Debug,
+    hash, iter, option,
+    sync::atomic::{AtomicU64, Ordering},
+};
 
-use std::{collections::{HashMap, HashSet, LinkedList}, fmt::{Debug, Display}, hash};
+use self::csr::CsrGraph;
+use self::filter::{AllEdges, AllVertices, Filtered};
+use self::observe::{GraphEvent, Observers};
+use self::traits::{EdgeWeight, GraphBase, Neighbors, VertexSet};
+use self::transpose::Transposed;
 
-#[derive(Debug)]
-pub struct Graph<K, T> {
+/// Directed graph with edge values `T` and optional per-vertex payloads `V`.
+#[cfg_attr(
+    feature = "serde",
+    doc = "",
+    doc = "With the `serde` feature it serializes to a list of vertices and a list of edges, \
+           described in [`serialize`]."
+)]
+#[derive(Debug, Clone)]
+pub struct Graph<K, T, V = ()> {
     content: HashMap<K, LinkedList<(K, T)>>,
+    /// Reverse index of `content`: for every vertex, the `(from, weight)` pairs of its incoming edges.
+    incoming: HashMap<K, LinkedList<(K, T)>>,
+    vertex_data: HashMap<K, V>,
+    observers: Observers<K, T>,
+    /// Replaced with a fresh number whenever a vertex or edge changes, so an
+    /// [`Undo`](transaction::Undo) can tell whether the graph is still as it left it.
+    revision: u64,
+}
+
+/// Source of [`Graph`] revisions, shared by all graphs so that no two states get the same one.
+static REVISIONS: AtomicU64 = AtomicU64::new(0);
+
+fn next_revision() -> u64 {
+    REVISIONS.fetch_add(1, Ordering::Relaxed)
 }
 
 impl<K, T> Graph<K, T>
 where
-    K: hash::Hash + Eq + Copy + Display + Debug + Ord,
-    T: Debug + Display,
+    K: hash::Hash + Eq + Copy + Debug + Ord,
+    T: Debug + Clone,
 {
     pub fn new() -> Graph<K, T> {
+        Graph::with_vertex_data()
+    }
+}
+
+impl<K, T, V> Graph<K, T, V>
+where
+    K: hash::Hash + Eq + Copy + Debug + Ord,
+    T: Debug + Clone,
+{
+    /// Creates an empty graph whose vertices can carry a `V` payload.
+    pub fn with_vertex_data() -> Graph<K, T, V> {
         Graph {
             content: HashMap::new(),
+            incoming: HashMap::new(),
+            vertex_data: HashMap::new(),
+            observers: Observers::default(),
+            revision: next_revision(),
+        }
+    }
+
+    fn from_parts(
+        content: HashMap<K, LinkedList<(K, T)>>,
+        vertex_data: HashMap<K, V>,
+    ) -> Graph<K, T, V> {
+        let mut incoming: HashMap<K, LinkedList<(K, T)>> = content
+            .keys()
+            .map(|vertex| (*vertex, LinkedList::new()))
+            .collect();
+
+        content.iter().for_each(|(from, edge_list)| {
+            edge_list.iter().for_each(|(to, value)| {
+                incoming
+                    .entry(*to)
+                    .or_default()
+                    .push_back((*from, value.clone()))
+            })
+        });
+
+        Graph {
+            content,
+            incoming,
+            vertex_data,
+            observers: Observers::default(),
+            revision: next_revision(),
         }
     }
 
@@ -25,8 +114,85 @@ where
         self.content.is_empty()
     }
 
+    /// Inserts `vertex` with no outgoing edges. If it already existed, its outgoing
+    /// edges are removed and returned.
     pub fn insert_vertex(&mut self, vertex: K) -> Option<LinkedList<(K, T)>> {
-        self.content.insert(vertex, LinkedList::new())
+        self.revision = next_revision();
+        self.incoming.entry(vertex).or_default();
+        let Some(previous) = self.content.insert(vertex, LinkedList::new()) else {
+            self.observers.emit(GraphEvent::VertexAdded(vertex));
+            return None;
+        };
+
+        previous.iter().for_each(|(to, value)| {
+            remove_all(self.incoming.get_mut(to).unwrap(), &vertex);
+            self.observers.emit(GraphEvent::EdgeRemoved {
+                from: vertex,
+                to: *to,
+                value,
+            });
+        });
+
+        Some(previous)
+    }
+
+    /// Inserts `vertex` like [`Graph::insert_vertex`] and attaches `data` to it.
+    pub fn insert_vertex_with_data(&mut self, vertex: K, data: V) -> Option<LinkedList<(K, T)>> {
+        self.vertex_data.insert(vertex, data);
+        self.insert_vertex(vertex)
+    }
+
+    pub fn vertex_data(&self, vertex: &K) -> Option<&V> {
+        self.vertex_data.get(vertex)
+    }
+
+    pub fn vertex_data_mut(&mut self, vertex: &K) -> Option<&mut V> {
+        self.vertex_data.get_mut(vertex)
+    }
+
+    /// Attaches `data` to an existing vertex, replacing any previous payload.
+    pub fn set_vertex_data(&mut self, vertex: &K, data: V) -> Option<()> {
+        self.vertex_exists(vertex).then(|| {
+            self.vertex_data.insert(*vertex, data);
+        })
+    }
+
+    pub fn take_vertex_data(&mut self, vertex: &K) -> Option<V> {
+        self.vertex_data.remove(vertex)
+    }
+
+    /// Removes `vertex` together with every edge touching it, returning its outgoing edges.
+    pub fn remove_vertex(&mut self, vertex: &K) -> Option<LinkedList<(K, T)>> {
+        let outgoing = self.content.remove(vertex)?;
+        let incoming = self.incoming.remove(vertex).unwrap_or_default();
+        self.revision = next_revision();
+        self.vertex_data.remove(vertex);
+
+        outgoing.iter().for_each(|(to, value)| {
+            if to != vertex {
+                remove_all(self.incoming.get_mut(to).unwrap(), vertex);
+            }
+            self.observers.emit(GraphEvent::EdgeRemoved {
+                from: *vertex,
+                to: *to,
+                value,
+            });
+        });
+
+        incoming
+            .iter()
+            .filter(|(from, _)| from != vertex)
+            .for_each(|(from, value)| {
+                remove_all(self.content.get_mut(from).unwrap(), vertex);
+                self.observers.emit(GraphEvent::EdgeRemoved {
+                    from: *from,
+                    to: *vertex,
+                    value,
+                });
+            });
+
+        self.observers.emit(GraphEvent::VertexRemoved(*vertex));
+        Some(outgoing)
     }
 
     pub fn vertex_exists(&self, vertex: &K) -> bool {
@@ -38,7 +204,49 @@ where
     }
 
     pub fn get_vertices(&self) -> Vec<&K> {
-        Vec::from_iter(self.content.keys())
+        Vec::from_iter(self.vertices())
+    }
+
+    pub fn vertices(&self) -> impl Iterator<Item = &K> {
+        self.content.keys()
+    }
+
+    /// Every edge as `(from, to, value)`.
+    pub fn edges(&self) -> impl Iterator<Item = (&K, &K, &T)> {
+        self.content.iter().flat_map(|(from, edge_list)| {
+            edge_list.iter().map(move |(to, value)| (from, to, value))
+        })
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.content.values().map(LinkedList::len).sum()
+    }
+
+    /// Outgoing edges of `vertex` as `(to, value)`. Unknown vertices have none.
+    pub fn edges_from(&self, vertex: &K) -> impl Iterator<Item = (&K, &T)> {
+        self.content
+            .get(vertex)
+            .into_iter()
+            .flatten()
+            .map(|(to, value)| (to, value))
+    }
+
+    /// Targets of the outgoing edges of `vertex`, repeated for parallel edges.
+    pub fn neighbors(&self, vertex: &K) -> impl Iterator<Item = &K> {
+        self.edges_from(vertex).map(|(to, _)| to)
+    }
+
+    pub fn out_degree(&self, vertex: &K) -> usize {
+        self.content.get(vertex).map_or(0, LinkedList::len)
+    }
+
+    pub fn in_degree(&self, vertex: &K) -> usize {
+        self.incoming.get(vertex).map_or(0, LinkedList::len)
+    }
+
+    /// Incoming plus outgoing edges; a self-loop counts twice.
+    pub fn degree(&self, vertex: &K) -> usize {
+        self.in_degree(vertex) + self.out_degree(vertex)
     }
 
     pub fn adjacency_list(&self, vertex: &K) -> Option<&LinkedList<(K, T)>> {
@@ -49,14 +257,77 @@ where
         self.content.get_mut(vertex)
     }
 
+    pub fn in_edges(&self, vertex: &K) -> Option<&LinkedList<(K, T)>> {
+        self.incoming.get(vertex)
+    }
+
     pub fn insert_edge(&mut self, from: K, to: K, value: T) -> Option<()> {
         (self.vertex_exists(&from) && self.vertex_exists(&to)).then(|| {
+            self.revision = next_revision();
+            self.incoming
+                .get_mut(&to)
+                .unwrap()
+                .push_front((from, value.clone()));
+            self.observers.emit(GraphEvent::EdgeAdded {
+                from,
+                to,
+                value: &value,
+            });
             self.adjacency_list_mut(&from)
                 .unwrap()
                 .push_front((to, value))
         })
     }
 
+    /// Value of the most recently inserted `from -> to` edge.
+    pub fn edge_data(&self, from: &K, to: &K) -> Option<&T> {
+        self.adjacency_list(from)?
+            .iter()
+            .find(|(neighbor, _)| neighbor == to)
+            .map(|(_, value)| value)
+    }
+
+    /// Replaces the value of the most recently inserted `from -> to` edge, returning the old one.
+    pub fn set_edge_data(&mut self, from: &K, to: &K, value: T) -> Option<T> {
+        let (_, incoming_value) = self
+            .incoming
+            .get_mut(to)?
+            .iter_mut()
+            .find(|(neighbor, _)| neighbor == from)?;
+        *incoming_value = value.clone();
+
+        let (_, outgoing_value) = self
+            .content
+            .get_mut(from)?
+            .iter_mut()
+            .find(|(neighbor, _)| neighbor == to)?;
+        let old = std::mem::replace(outgoing_value, value);
+        self.revision = next_revision();
+
+        self.observers.emit(GraphEvent::EdgeUpdated {
+            from: *from,
+            to: *to,
+            old: &old,
+            new: outgoing_value,
+        });
+        Some(old)
+    }
+
+    /// Removes one `from -> to` edge, returning its value. With parallel edges the
+    /// most recently inserted one goes first.
+    pub fn remove_edge(&mut self, from: &K, to: &K) -> Option<T> {
+        let (_, value) = remove_first(self.content.get_mut(from)?, to)?;
+        remove_first(self.incoming.get_mut(to).unwrap(), from);
+        self.revision = next_revision();
+
+        self.observers.emit(GraphEvent::EdgeRemoved {
+            from: *from,
+            to: *to,
+            value: &value,
+        });
+        Some(value)
+    }
+
     pub fn edge_exists(&self, from: &K, to: &K) -> bool {
         self.vertex_exists(from)
             && self
@@ -67,34 +338,191 @@ where
     }
 
     pub fn out_neighbors(&self, vertex: &K) -> Option<HashSet<&K>> {
+        self.vertex_exists(vertex)
+            .then(|| self.neighbors(vertex).collect())
+    }
+
+    pub fn in_neighbors(&self, vertex: &K) -> Option<HashSet<&K>> {
         Some(
-            self.adjacency_list(&vertex)?
+            self.in_edges(vertex)?
                 .iter()
-                .map(|(out_neighbor, _)| out_neighbor)
+                .map(|(in_neighbor, _)| in_neighbor)
+                .filter(|in_neighbor| *in_neighbor != vertex)
                 .collect(),
         )
     }
 
-    pub fn in_neighbors(&self, vertex: &K) -> Option<HashSet<&K>> {
-        if !self.vertex_exists(vertex) {
-            return None;
+    /// A view of the graph with every edge reversed. Nothing is copied.
+    pub fn transpose(&self) -> Transposed<'_, K, T, V> {
+        Transposed::new(self)
+    }
+
+    /// A view hiding the vertices and edges rejected by the predicates. Nothing is copied.
+    pub fn filter<VF, EF>(&self, vertex_filter: VF, edge_filter: EF) -> Filtered<'_, Self, VF, EF>
+    where
+        VF: Fn(&K) -> bool,
+        EF: Fn(&K, &K, &T) -> bool,
+    {
+        Filtered::new(self, vertex_filter, edge_filter)
+    }
+
+    pub fn filter_vertices<VF>(&self, vertex_filter: VF) -> Filtered<'_, Self, VF, AllEdges<K, T>>
+    where
+        VF: Fn(&K) -> bool,
+    {
+        Filtered::new(self, vertex_filter, |_, _, _| true)
+    }
+
+    pub fn filter_edges<EF>(&self, edge_filter: EF) -> Filtered<'_, Self, AllVertices<K>, EF>
+    where
+        EF: Fn(&K, &K, &T) -> bool,
+    {
+        Filtered::new(self, |_| true, edge_filter)
+    }
+
+    /// A new graph with the given vertices and every edge running between them.
+    /// Keys that are not in the graph are ignored.
+    pub fn induced_subgraph(&self, vertices: impl IntoIterator<Item = K>) -> Graph<K, T, V>
+    where
+        V: Clone,
+    {
+        let mut subgraph = Graph::with_vertex_data();
+        vertices
+            .into_iter()
+            .filter(|vertex| self.vertex_exists(vertex))
+            .for_each(|vertex| subgraph.copy_vertex(self, vertex));
+
+        self.content.iter().for_each(|(from, edge_list)| {
+            edge_list.iter().rev().for_each(|(to, value)| {
+                subgraph.insert_edge(*from, *to, value.clone());
+            })
+        });
+
+        subgraph
+    }
+
+    /// A new graph with the given `(from, to)` edges and their endpoints. Parallel edges
+    /// between a listed pair are all kept; pairs without an edge are ignored.
+    pub fn edge_subgraph(&self, edges: impl IntoIterator<Item = (K, K)>) -> Graph<K, T, V>
+    where
+        V: Clone,
+    {
+        let mut subgraph = Graph::with_vertex_data();
+        for (from, to) in edges {
+            if !self.edge_exists(&from, &to) || subgraph.edge_exists(&from, &to) {
+                continue;
+            }
+
+            [from, to].into_iter().for_each(|vertex| {
+                if !subgraph.vertex_exists(&vertex) {
+                    subgraph.copy_vertex(self, vertex);
+                }
+            });
+            self.content[&from]
+                .iter()
+                .rev()
+                .filter(|(neighbor, _)| *neighbor == to)
+                .for_each(|(_, value)| {
+                    subgraph.insert_edge(from, to, value.clone());
+                });
+        }
+
+        subgraph
+    }
+
+    fn copy_vertex(&mut self, source: &Graph<K, T, V>, vertex: K)
+    where
+        V: Clone,
+    {
+        match source.vertex_data(&vertex) {
+            Some(data) => self.insert_vertex_with_data(vertex, data.clone()),
+            None => self.insert_vertex(vertex),
         };
+    }
 
-        Some(
-            self.content
-                .keys()
-                .filter_map(|key| {
-                    if key != vertex && self.edge_exists(key, vertex) {
-                        Some(key)
-                    } else {
-                        None
-                    }
-                })
-                .collect(),
-        )
+    /// Converts the graph into an immutable CSR representation for traversal-heavy work.
+    /// Vertex payloads are not carried over, and edges to vertices without an adjacency
+    /// entry are dropped.
+    pub fn freeze(&self) -> CsrGraph<K, T> {
+        CsrGraph::from_graph(self)
     }
+}
+
+fn remove_first<K: Eq, T>(list: &mut LinkedList<(K, T)>, key: &K) -> Option<(K, T)> {
+    let position = list.iter().position(|(other, _)| other == key)?;
+    let mut tail = list.split_off(position);
+    let removed = tail.pop_front();
+    list.append(&mut tail);
+    removed
+}
 
+fn remove_all<K: Eq, T>(list: &mut LinkedList<(K, T)>, key: &K) {
+    while remove_first(list, key).is_some() {}
+}
+
+impl<K, T, V> Default for Graph<K, T, V>
+where
+    K: hash::Hash + Eq + Copy + Debug + Ord,
+    T: Debug + Clone,
+{
+    fn default() -> Self {
+        Self::with_vertex_data()
+    }
+}
 
+impl<K, T, V> GraphBase for Graph<K, T, V>
+where
+    K: hash::Hash + Eq + Copy,
+{
+    type Key = K;
+    type Weight = T;
+}
+
+impl<K, T, V> VertexSet for Graph<K, T, V>
+where
+    K: hash::Hash + Eq + Copy,
+{
+    type VertexKeys<'a>
+        = iter::Copied<hash_map::Keys<'a, K, LinkedList<(K, T)>>>
+    where
+        Self: 'a;
+
+    fn vertex_keys(&self) -> Self::VertexKeys<'_> {
+        self.content.keys().copied()
+    }
+
+    fn has_vertex(&self, vertex: &K) -> bool {
+        self.content.contains_key(vertex)
+    }
+}
+
+impl<K, T, V> Neighbors for Graph<K, T, V>
+where
+    K: hash::Hash + Eq + Copy,
+    T: Clone,
+{
+    type Successors<'a>
+        = iter::Cloned<iter::Flatten<option::IntoIter<&'a LinkedList<(K, T)>>>>
+    where
+        Self: 'a;
+
+    fn successors(&self, vertex: &K) -> Self::Successors<'_> {
+        self.content.get(vertex).into_iter().flatten().cloned()
+    }
+}
+
+impl<K, T, V> EdgeWeight for Graph<K, T, V>
+where
+    K: hash::Hash + Eq + Copy,
+    T: Clone,
+{
+    fn edge_weight(&self, from: &K, to: &K) -> Option<T> {
+        self.content
+            .get(from)?
+            .iter()
+            .find(|(neighbor, _)| neighbor == to)
+            .map(|(_, value)| value.clone())
+    }
 }
 
 #[cfg(test)]
@@ -106,7 +534,7 @@ mod tests {
     #[test]
     fn is_empty() {
         let graph = Graph::<i64, i64>::new();
-        assert_eq!(graph.is_empty(), true);
+        assert!(graph.is_empty());
     }
 
     #[test]
@@ -114,7 +542,7 @@ mod tests {
         let mut graph = Graph::<i64, i64>::new();
         let vertex = 10;
         assert_eq!(graph.insert_vertex(vertex), None);
-        assert_eq!(graph.is_empty(), false);
+        assert!(!graph.is_empty());
     }
 
     #[test]
@@ -123,7 +551,7 @@ mod tests {
         let vertex = 10;
         let to = 12;
         graph.insert_edge(vertex, to, 0);
-        assert_eq!(graph.is_empty(), true);
+        assert!(graph.is_empty());
     }
 
     #[test]
@@ -170,8 +598,8 @@ mod tests {
         let result = graph.insert_edge(vertex, to, 0);
         assert!(result.is_some());
 
-        assert_eq!(graph.edge_exists(&vertex, &to), true);
-        assert_eq!(graph.edge_exists(&to, &vertex), false);
+        assert!(graph.edge_exists(&vertex, &to));
+        assert!(!graph.edge_exists(&to, &vertex));
     }
 
     #[test]
@@ -205,7 +633,206 @@ mod tests {
     }
 
     #[test]
-    fn read_from_file() {
-        
+    fn in_edges() {
+        let mut graph = Graph::<i64, i64>::new();
+        graph.insert_vertex(10);
+        graph.insert_vertex(12);
+        graph.insert_vertex(13);
+        graph.insert_edge(12, 10, 4);
+        graph.insert_edge(13, 10, 5);
+
+        assert_eq!(
+            *graph.in_edges(&10).unwrap(),
+            LinkedList::from([(13, 5), (12, 4)])
+        );
+        assert!(graph.in_edges(&12).unwrap().is_empty());
+        assert_eq!(graph.in_edges(&11), None);
     }
+
+    #[test]
+    fn remove_edge() {
+        let mut graph = Graph::<i64, i64>::new();
+        graph.insert_vertex(10);
+        graph.insert_vertex(12);
+        graph.insert_edge(10, 12, 1);
+        graph.insert_edge(10, 12, 2);
+
+        assert_eq!(graph.remove_edge(&10, &12), Some(2));
+        assert_eq!(*graph.in_edges(&12).unwrap(), LinkedList::from([(10, 1)]));
+        assert_eq!(graph.remove_edge(&10, &12), Some(1));
+        assert_eq!(graph.remove_edge(&10, &12), None);
+        assert!(graph.in_neighbors(&12).unwrap().is_empty());
+    }
+
+    #[test]
+    fn remove_vertex() {
+        let mut graph = Graph::<i64, i64>::new();
+        graph.insert_vertex(10);
+        graph.insert_vertex(12);
+        graph.insert_vertex(13);
+        graph.insert_edge(10, 12, 1);
+        graph.insert_edge(12, 13, 2);
+        graph.insert_edge(13, 12, 3);
+        graph.insert_edge(12, 12, 4);
+
+        assert_eq!(
+            graph.remove_vertex(&12),
+            Some(LinkedList::from([(12, 4), (13, 2)]))
+        );
+        assert!(!graph.vertex_exists(&12));
+        assert!(graph.adjacency_list(&10).unwrap().is_empty());
+        assert!(graph.adjacency_list(&13).unwrap().is_empty());
+        assert!(graph.in_edges(&13).unwrap().is_empty());
+        assert_eq!(graph.remove_vertex(&12), None);
+    }
+
+    #[test]
+    fn reinsert_vertex_clears_incoming() {
+        let mut graph = Graph::<i64, i64>::new();
+        graph.insert_vertex(10);
+        graph.insert_vertex(12);
+        graph.insert_edge(10, 12, 1);
+
+        assert_eq!(graph.insert_vertex(10), Some(LinkedList::from([(12, 1)])));
+        assert!(graph.in_neighbors(&12).unwrap().is_empty());
+    }
+
+    #[test]
+    fn iterators() {
+        let mut graph = Graph::<i64, i64>::new();
+        graph.insert_vertex(10);
+        graph.insert_vertex(12);
+        graph.insert_vertex(13);
+        graph.insert_edge(10, 12, 1);
+        graph.insert_edge(10, 13, 2);
+        graph.insert_edge(13, 10, 3);
+        graph.insert_edge(13, 13, 4);
+
+        let mut vertices = Vec::from_iter(graph.vertices().copied());
+        vertices.sort();
+        assert_eq!(vertices, vec![10, 12, 13]);
+
+        let mut edges = Vec::from_iter(graph.edges().map(|(from, to, value)| (*from, *to, *value)));
+        edges.sort();
+        assert_eq!(
+            edges,
+            vec![(10, 12, 1), (10, 13, 2), (13, 10, 3), (13, 13, 4)]
+        );
+        assert_eq!(graph.edge_count(), 4);
+
+        assert_eq!(
+            Vec::from_iter(graph.edges_from(&10)),
+            vec![(&13, &2), (&12, &1)]
+        );
+        assert_eq!(Vec::from_iter(graph.neighbors(&13)), vec![&13, &10]);
+        assert_eq!(graph.neighbors(&11).count(), 0);
+    }
+
+    #[test]
+    fn degrees() {
+        let mut graph = Graph::<i64, i64>::new();
+        graph.insert_vertex(10);
+        graph.insert_vertex(12);
+        graph.insert_edge(10, 12, 1);
+        graph.insert_edge(10, 12, 2);
+        graph.insert_edge(12, 12, 3);
+
+        assert_eq!(graph.out_degree(&10), 2);
+        assert_eq!(graph.in_degree(&10), 0);
+        assert_eq!(graph.out_degree(&12), 1);
+        assert_eq!(graph.in_degree(&12), 3);
+        assert_eq!(graph.degree(&12), 4);
+        assert_eq!(graph.degree(&11), 0);
+    }
+
+    #[test]
+    fn induced_subgraph() {
+        let mut graph = Graph::<i64, i64, &str>::with_vertex_data();
+        graph.insert_vertex_with_data(10, "ten");
+        graph.insert_vertex(12);
+        graph.insert_vertex(13);
+        graph.insert_edge(10, 12, 1);
+        graph.insert_edge(10, 12, 2);
+        graph.insert_edge(12, 13, 3);
+        graph.insert_edge(13, 10, 4);
+
+        let subgraph = graph.induced_subgraph([10, 12, 99]);
+        assert_eq!(subgraph.vertex_count(), 2);
+        assert_eq!(subgraph.vertex_data(&10), Some(&"ten"));
+        assert_eq!(subgraph.adjacency_list(&10), graph.adjacency_list(&10));
+        assert!(subgraph.adjacency_list(&12).unwrap().is_empty());
+        assert_eq!(subgraph.in_degree(&10), 0);
+    }
+
+    #[test]
+    fn edge_subgraph() {
+        let mut graph = Graph::<i64, i64>::new();
+        graph.insert_vertex(10);
+        graph.insert_vertex(12);
+        graph.insert_vertex(13);
+        graph.insert_edge(10, 12, 1);
+        graph.insert_edge(10, 12, 2);
+        graph.insert_edge(12, 13, 3);
+        graph.insert_edge(13, 10, 4);
+
+        let subgraph = graph.edge_subgraph([(10, 12), (10, 12), (12, 10)]);
+        assert_eq!(subgraph.vertex_count(), 2);
+        assert_eq!(subgraph.edge_count(), 2);
+        assert_eq!(subgraph.adjacency_list(&10), graph.adjacency_list(&10));
+        assert!(!subgraph.vertex_exists(&13));
+    }
+
+    #[test]
+    fn vertex_data() {
+        let mut graph = Graph::<i64, i64, &str>::with_vertex_data();
+        graph.insert_vertex_with_data(10, "ten");
+        graph.insert_vertex(12);
+
+        assert_eq!(graph.vertex_data(&10), Some(&"ten"));
+        assert_eq!(graph.vertex_data(&12), None);
+
+        *graph.vertex_data_mut(&10).unwrap() = "TEN";
+        assert_eq!(graph.set_vertex_data(&12, "twelve"), Some(()));
+        assert_eq!(graph.set_vertex_data(&13, "thirteen"), None);
+        assert_eq!(graph.vertex_data(&10), Some(&"TEN"));
+        assert_eq!(graph.take_vertex_data(&12), Some("twelve"));
+
+        graph.remove_vertex(&10);
+        assert_eq!(graph.vertex_data(&10), None);
+    }
+
+    #[test]
+    fn edge_data() {
+        #[derive(Debug, Clone, PartialEq)]
+        struct Road {
+            length: u64,
+            closed: bool,
+        }
+
+        let mut graph = Graph::<i64, Road>::new();
+        graph.insert_vertex(10);
+        graph.insert_vertex(12);
+        let road = Road {
+            length: 5,
+            closed: false,
+        };
+        graph.insert_edge(10, 12, road.clone());
+
+        assert_eq!(graph.edge_data(&10, &12), Some(&road));
+        assert_eq!(graph.edge_data(&12, &10), None);
+
+        let closed = Road {
+            length: 5,
+            closed: true,
+        };
+        assert_eq!(graph.set_edge_data(&10, &12, closed.clone()), Some(road));
+        assert_eq!(graph.edge_data(&10, &12), Some(&closed));
+        assert_eq!(
+            *graph.in_edges(&12).unwrap(),
+            LinkedList::from([(10, closed)])
+        );
+    }
+
+    #[test]
+    fn read_from_file() {}
 }
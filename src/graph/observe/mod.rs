@@ -0,0 +1,181 @@
+//! Change notifications for [`Graph`], so derived data can be invalidated precisely.
+
+use std::{
+    fmt::{self, Debug},
+    hash,
+};
+
+use super::Graph;
+
+/// A single change to a graph, emitted as it is applied.
+///
+/// Removing a vertex first emits `EdgeRemoved` for every edge touching it, then
+/// `VertexRemoved`. Re-inserting an existing vertex emits `EdgeRemoved` for each of
+/// its outgoing edges. Changes to vertex payloads are not reported.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GraphEvent<'a, K, T> {
+    VertexAdded(K),
+    VertexRemoved(K),
+    EdgeAdded {
+        from: K,
+        to: K,
+        value: &'a T,
+    },
+    EdgeRemoved {
+        from: K,
+        to: K,
+        value: &'a T,
+    },
+    EdgeUpdated {
+        from: K,
+        to: K,
+        old: &'a T,
+        new: &'a T,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ObserverId(u64);
+
+type Callback<K, T> = Box<dyn FnMut(&GraphEvent<'_, K, T>) + Send + Sync>;
+
+/// The callbacks registered on one graph. Clones start out empty.
+pub(crate) struct Observers<K, T> {
+    next_id: u64,
+    callbacks: Vec<(ObserverId, Callback<K, T>)>,
+}
+
+impl<K, T> Observers<K, T> {
+    pub(crate) fn emit(&mut self, event: GraphEvent<'_, K, T>) {
+        self.callbacks
+            .iter_mut()
+            .for_each(|(_, callback)| callback(&event));
+    }
+}
+
+impl<K, T> Default for Observers<K, T> {
+    fn default() -> Self {
+        Observers {
+            next_id: 0,
+            callbacks: Vec::new(),
+        }
+    }
+}
+
+impl<K, T> Clone for Observers<K, T> {
+    fn clone(&self) -> Self {
+        Observers::default()
+    }
+}
+
+impl<K, T> Debug for Observers<K, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Observers({})", self.callbacks.len())
+    }
+}
+
+impl<K, T, V> Graph<K, T, V>
+where
+    K: hash::Hash + Eq + Copy + Debug + Ord,
+    T: Debug + Clone,
+{
+    /// Calls `callback` for every later change of this graph. Clones of the graph do not
+    /// inherit observers.
+    pub fn subscribe(
+        &mut self,
+        callback: impl FnMut(&GraphEvent<'_, K, T>) + Send + Sync + 'static,
+    ) -> ObserverId {
+        let id = ObserverId(self.observers.next_id);
+        self.observers.next_id += 1;
+        self.observers.callbacks.push((id, Box::new(callback)));
+        id
+    }
+
+    /// Returns whether `id` was still subscribed.
+    pub fn unsubscribe(&mut self, id: ObserverId) -> bool {
+        let callbacks = &mut self.observers.callbacks;
+        let count = callbacks.len();
+        callbacks.retain(|(other, _)| *other != id);
+        callbacks.len() != count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::GraphEvent;
+    use crate::graph::Graph;
+
+    fn record(graph: &mut Graph<i64, u64>) -> Arc<Mutex<Vec<String>>> {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&events);
+        graph.subscribe(move |event| {
+            let line = match event {
+                GraphEvent::VertexAdded(vertex) => format!("+{vertex}"),
+                GraphEvent::VertexRemoved(vertex) => format!("-{vertex}"),
+                GraphEvent::EdgeAdded { from, to, value } => format!("+{from}->{to} {value}"),
+                GraphEvent::EdgeRemoved { from, to, value } => format!("-{from}->{to} {value}"),
+                GraphEvent::EdgeUpdated { from, to, old, new } => {
+                    format!("~{from}->{to} {old}=>{new}")
+                }
+            };
+            sink.lock().unwrap().push(line);
+        });
+        events
+    }
+
+    #[test]
+    fn events() {
+        let mut graph = Graph::new();
+        let events = record(&mut graph);
+
+        graph.insert_vertex(0);
+        graph.insert_vertex(1);
+        graph.insert_edge(0, 1, 10);
+        graph.insert_edge(1, 0, 20);
+        graph.insert_edge(1, 1, 5);
+        graph.insert_edge(0, 2, 1);
+        graph.set_edge_data(&0, &1, 15);
+        graph.remove_edge(&0, &1);
+        graph.remove_edge(&0, &1);
+        graph.remove_vertex(&1);
+        graph.insert_vertex(1);
+        graph.insert_edge(1, 0, 3);
+        graph.insert_vertex(1);
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            [
+                "+0",
+                "+1",
+                "+0->1 10",
+                "+1->0 20",
+                "+1->1 5",
+                "~0->1 10=>15",
+                "-0->1 15",
+                "-1->1 5",
+                "-1->0 20",
+                "-1",
+                "+1",
+                "+1->0 3",
+                "-1->0 3",
+            ]
+        );
+    }
+
+    #[test]
+    fn unsubscribe_and_clone() {
+        let mut graph = Graph::new();
+        let events = record(&mut graph);
+        let id = graph.subscribe(|_| panic!("unsubscribed observer called"));
+
+        assert!(graph.unsubscribe(id));
+        assert!(!graph.unsubscribe(id));
+        graph.insert_vertex(0);
+
+        let mut copy = graph.clone();
+        copy.insert_vertex(1);
+        assert_eq!(*events.lock().unwrap(), ["+0"]);
+    }
+}
@@ -0,0 +1,411 @@
+//! Set operations and products of graphs.
+//!
+//! Vertices are matched by key: the same key in both operands is the same vertex, and
+//! its payload is taken from the left operand when both carry one. Edges are matched by
+//! their `(from, to)` pair. Operations that take a `merge` function first fold the
+//! parallel edges of each operand into one value with it, so their results have at most
+//! one edge per pair; the others keep every parallel edge.
+
+use std::{collections::HashMap, fmt::Debug, hash};
+
+use super::Graph;
+
+impl<K, T, V> Graph<K, T, V>
+where
+    K: hash::Hash + Eq + Copy + Debug + Ord,
+    T: Debug + Clone,
+{
+    /// Every vertex and edge of both graphs. Edges present in both get the value
+    /// `merge(left, right)`; parallel edges are folded with `merge` as well.
+    pub fn union(&self, other: &Graph<K, T, V>, merge: impl Fn(&T, &T) -> T) -> Graph<K, T, V>
+    where
+        V: Clone,
+    {
+        let mut result = Graph::with_vertex_data();
+        self.vertices()
+            .chain(other.vertices())
+            .for_each(|vertex| result.copy_vertex_from(self, other, *vertex));
+
+        let right = other.merged_edges(&merge);
+        self.merged_edges(&merge)
+            .into_iter()
+            .for_each(|((from, to), value)| {
+                let value = match right.get(&(from, to)) {
+                    Some(other_value) => merge(&value, other_value),
+                    None => value,
+                };
+                result.insert_edge(from, to, value);
+            });
+        right.into_iter().for_each(|((from, to), value)| {
+            if !result.edge_exists(&from, &to) {
+                result.insert_edge(from, to, value);
+            }
+        });
+
+        result
+    }
+
+    /// Vertices and edges present in both graphs, with edge values `merge(left, right)`
+    /// after folding parallel edges with `merge`.
+    pub fn intersection(
+        &self,
+        other: &Graph<K, T, V>,
+        merge: impl Fn(&T, &T) -> T,
+    ) -> Graph<K, T, V>
+    where
+        V: Clone,
+    {
+        let mut result = Graph::with_vertex_data();
+        self.vertices()
+            .filter(|vertex| other.vertex_exists(vertex))
+            .for_each(|vertex| result.copy_vertex_from(self, other, *vertex));
+
+        let right = other.merged_edges(&merge);
+        self.merged_edges(&merge)
+            .into_iter()
+            .for_each(|((from, to), value)| {
+                if let Some(other_value) = right.get(&(from, to)) {
+                    result.insert_edge(from, to, merge(&value, other_value));
+                }
+            });
+
+        result
+    }
+
+    /// Every vertex of this graph with the edges whose `(from, to)` pair `other` does not
+    /// connect, parallel edges included.
+    pub fn difference<W, U>(&self, other: &Graph<K, W, U>) -> Graph<K, T, V>
+    where
+        W: Debug + Clone,
+        V: Clone,
+    {
+        let mut result = Graph::with_vertex_data();
+        self.vertices()
+            .for_each(|vertex| result.copy_vertex(self, *vertex));
+
+        self.edges().for_each(|(from, to, value)| {
+            if !other.edge_exists(from, to) {
+                result.insert_edge(*from, *to, value.clone());
+            }
+        });
+
+        result
+    }
+
+    /// Same vertices, with an edge between every ordered pair of distinct vertices that
+    /// are not connected here. New edges get the value `value(from, to)`.
+    pub fn complement(&self, value: impl Fn(&K, &K) -> T) -> Graph<K, T, V>
+    where
+        V: Clone,
+    {
+        let mut result = Graph::with_vertex_data();
+        self.vertices()
+            .for_each(|vertex| result.copy_vertex(self, *vertex));
+
+        self.vertices().for_each(|from| {
+            self.vertices()
+                .filter(|to| from != *to && !self.edge_exists(from, to))
+                .for_each(|to| {
+                    result.insert_edge(*from, *to, value(from, to));
+                })
+        });
+
+        result
+    }
+
+    /// Relational composition: an edge `u -> w` for every path `u -> v` here followed by
+    /// `v -> w` in `other`, valued `combine(first, second)`. When several paths, through
+    /// different middle vertices or parallel edges, connect the same pair, their values are
+    /// folded with `merge`.
+    pub fn compose(
+        &self,
+        other: &Graph<K, T, V>,
+        combine: impl Fn(&T, &T) -> T,
+        merge: impl Fn(&T, &T) -> T,
+    ) -> Graph<K, T, V>
+    where
+        V: Clone,
+    {
+        let mut result = Graph::with_vertex_data();
+        self.vertices()
+            .chain(other.vertices())
+            .for_each(|vertex| result.copy_vertex_from(self, other, *vertex));
+
+        let right = other.edges_by_source();
+        let mut composed: HashMap<(K, K), T> = HashMap::new();
+        self.edges().for_each(|(from, middle, first)| {
+            right
+                .get(middle)
+                .into_iter()
+                .flatten()
+                .for_each(|(to, second)| {
+                    let value = combine(first, second);
+                    composed
+                        .entry((*from, *to))
+                        .and_modify(|current| *current = merge(current, &value))
+                        .or_insert(value);
+                })
+        });
+
+        composed.into_iter().for_each(|((from, to), value)| {
+            result.insert_edge(from, to, value);
+        });
+
+        result
+    }
+
+    /// Cartesian product: vertex `(u, x)` connects to `(v, x)` for every edge `u -> v`
+    /// here, and to `(u, y)` for every edge `x -> y` in `other`, keeping that edge's value.
+    /// A product vertex carries a payload when both of its parts do.
+    pub fn cartesian_product<L, U>(&self, other: &Graph<L, T, U>) -> Graph<(K, L), T, (V, U)>
+    where
+        L: hash::Hash + Eq + Copy + Debug + Ord,
+        V: Clone,
+        U: Clone,
+    {
+        let mut result = Graph::product_vertices(self, other);
+
+        let (left, right) = (self.edges_by_source(), other.edges_by_source());
+        self.vertices().for_each(|u| {
+            other.vertices().for_each(|x| {
+                left.get(u).into_iter().flatten().for_each(|(v, value)| {
+                    result.insert_edge((*u, *x), (*v, *x), (*value).clone());
+                });
+                right.get(x).into_iter().flatten().for_each(|(y, value)| {
+                    result.insert_edge((*u, *x), (*u, *y), (*value).clone());
+                });
+            })
+        });
+
+        result
+    }
+
+    /// Tensor product: vertex `(u, x)` connects to `(v, y)` whenever `u -> v` is an edge
+    /// here and `x -> y` one in `other`, valued `combine(first, second)`. Parallel edges
+    /// give one product edge per combination.
+    pub fn tensor_product<L, U>(
+        &self,
+        other: &Graph<L, T, U>,
+        combine: impl Fn(&T, &T) -> T,
+    ) -> Graph<(K, L), T, (V, U)>
+    where
+        L: hash::Hash + Eq + Copy + Debug + Ord,
+        V: Clone,
+        U: Clone,
+    {
+        let mut result = Graph::product_vertices(self, other);
+
+        let right = Vec::from_iter(other.edges().map(|(x, y, value)| (*x, *y, value)));
+        self.edges().for_each(|(u, v, first)| {
+            right.iter().for_each(|(x, y, second)| {
+                result.insert_edge((*u, *x), (*v, *y), combine(first, second));
+            })
+        });
+
+        result
+    }
+
+    /// One value for every connected `(from, to)` pair, folding parallel edges with `merge`.
+    fn merged_edges(&self, merge: impl Fn(&T, &T) -> T) -> HashMap<(K, K), T> {
+        let mut edges: HashMap<(K, K), T> = HashMap::new();
+        self.edges().for_each(|(from, to, value)| {
+            edges
+                .entry((*from, *to))
+                .and_modify(|current| *current = merge(current, value))
+                .or_insert_with(|| value.clone());
+        });
+        edges
+    }
+
+    /// Every edge grouped by source vertex, as `(to, value)` pairs.
+    fn edges_by_source(&self) -> HashMap<K, Vec<(K, &T)>> {
+        let mut grouped: HashMap<K, Vec<(K, &T)>> = HashMap::new();
+        self.edges()
+            .for_each(|(from, to, value)| grouped.entry(*from).or_default().push((*to, value)));
+        grouped
+    }
+
+    fn copy_vertex_from(&mut self, left: &Graph<K, T, V>, right: &Graph<K, T, V>, vertex: K)
+    where
+        V: Clone,
+    {
+        if self.vertex_exists(&vertex) {
+            return;
+        }
+
+        match left.vertex_data(&vertex).or(right.vertex_data(&vertex)) {
+            Some(data) => self.insert_vertex_with_data(vertex, data.clone()),
+            None => self.insert_vertex(vertex),
+        };
+    }
+}
+
+impl<K, L, T, V, U> Graph<(K, L), T, (V, U)>
+where
+    K: hash::Hash + Eq + Copy + Debug + Ord,
+    L: hash::Hash + Eq + Copy + Debug + Ord,
+    T: Debug + Clone,
+    V: Clone,
+    U: Clone,
+{
+    fn product_vertices<W>(left: &Graph<K, T, V>, right: &Graph<L, W, U>) -> Self
+    where
+        W: Debug + Clone,
+    {
+        let mut result = Graph::with_vertex_data();
+
+        left.vertices().for_each(|u| {
+            right.vertices().for_each(|x| {
+                let vertex = (*u, *x);
+                match (left.vertex_data(u), right.vertex_data(x)) {
+                    (Some(a), Some(b)) => {
+                        result.insert_vertex_with_data(vertex, (a.clone(), b.clone()))
+                    }
+                    _ => result.insert_vertex(vertex),
+                };
+            })
+        });
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::Graph;
+
+    fn path(vertices: &[i64], weight: u64) -> Graph<i64, u64> {
+        let mut graph = Graph::new();
+        vertices.iter().for_each(|vertex| {
+            graph.insert_vertex(*vertex);
+        });
+        vertices.windows(2).for_each(|pair| {
+            graph.insert_edge(pair[0], pair[1], weight);
+        });
+        graph
+    }
+
+    fn sorted_edges<K: Copy + Ord + std::hash::Hash + std::fmt::Debug, V>(
+        graph: &Graph<K, u64, V>,
+    ) -> Vec<(K, K, u64)> {
+        let mut edges = Vec::from_iter(graph.edges().map(|(from, to, value)| (*from, *to, *value)));
+        edges.sort();
+        edges
+    }
+
+    #[test]
+    fn union() {
+        let mut left = Graph::<i64, u64, &str>::with_vertex_data();
+        left.insert_vertex_with_data(0, "left");
+        left.insert_vertex(1);
+        left.insert_edge(0, 1, 10);
+
+        let mut right = Graph::<i64, u64, &str>::with_vertex_data();
+        right.insert_vertex_with_data(0, "right");
+        right.insert_vertex_with_data(1, "one");
+        right.insert_vertex(2);
+        right.insert_edge(0, 1, 4);
+        right.insert_edge(1, 2, 7);
+
+        let union = left.union(&right, |a, b| *a.min(b));
+        assert_eq!(union.vertex_count(), 3);
+        assert_eq!(union.vertex_data(&0), Some(&"left"));
+        assert_eq!(union.vertex_data(&1), Some(&"one"));
+        assert_eq!(sorted_edges(&union), vec![(0, 1, 4), (1, 2, 7)]);
+    }
+
+    #[test]
+    fn intersection_and_difference() {
+        let left = path(&[0, 1, 2, 3], 1);
+        let right = path(&[1, 2, 3, 4], 2);
+
+        let intersection = left.intersection(&right, |a, b| a + b);
+        assert_eq!(intersection.vertex_count(), 3);
+        assert_eq!(sorted_edges(&intersection), vec![(1, 2, 3), (2, 3, 3)]);
+
+        let difference = left.difference(&right);
+        assert_eq!(difference.vertex_count(), 4);
+        assert_eq!(sorted_edges(&difference), vec![(0, 1, 1)]);
+    }
+
+    #[test]
+    fn complement() {
+        let graph = path(&[0, 1, 2], 1);
+        let complement = graph.complement(|from, to| (from * 10 + to) as u64);
+
+        assert_eq!(
+            sorted_edges(&complement),
+            vec![(0, 2, 2), (1, 0, 10), (2, 0, 20), (2, 1, 21)]
+        );
+        assert_eq!(complement.complement(|_, _| 1).edge_count(), 2);
+    }
+
+    #[test]
+    fn compose() {
+        let mut left = path(&[0, 1], 1);
+        left.insert_vertex(2);
+        left.insert_edge(0, 2, 5);
+        let mut right = path(&[1, 3], 2);
+        right.insert_vertex(2);
+        right.insert_edge(2, 3, 1);
+
+        let composed = left.compose(&right, |a, b| a + b, |a, b| *a.min(b));
+        assert_eq!(sorted_edges(&composed), vec![(0, 3, 3)]);
+        assert_eq!(composed.vertex_count(), 4);
+    }
+
+    #[test]
+    fn products() {
+        let left = path(&[0, 1], 1);
+        let right = path(&[0, 1, 2], 2);
+
+        let cartesian = left.cartesian_product(&right);
+        assert_eq!(cartesian.vertex_count(), 6);
+        assert_eq!(cartesian.edge_count(), 3 + 2 * 2);
+        assert_eq!(cartesian.edge_data(&(0, 1), &(1, 1)), Some(&1));
+        assert_eq!(cartesian.edge_data(&(1, 1), &(1, 2)), Some(&2));
+
+        let tensor = left.tensor_product(&right, |a, b| a * b);
+        assert_eq!(tensor.vertex_count(), 6);
+        assert_eq!(
+            sorted_edges(&tensor),
+            vec![((0, 0), (1, 1), 2), ((0, 1), (1, 2), 2)]
+        );
+    }
+
+    #[test]
+    fn parallel_edges() {
+        let mut left = path(&[0, 1], 1);
+        left.insert_edge(0, 1, 4);
+        let mut right = path(&[0, 1, 2], 2);
+        right.insert_edge(1, 2, 3);
+
+        let union = left.union(&right, |a, b| a + b);
+        assert_eq!(sorted_edges(&union), vec![(0, 1, 7), (1, 2, 5)]);
+
+        let intersection = left.intersection(&right, |a, b| *a.max(b));
+        assert_eq!(sorted_edges(&intersection), vec![(0, 1, 4)]);
+
+        let difference = left.difference(&path(&[1, 0], 1));
+        assert_eq!(sorted_edges(&difference), vec![(0, 1, 1), (0, 1, 4)]);
+
+        let composed = left.compose(&right, |a, b| a * b, |a, b| a + b);
+        assert_eq!(sorted_edges(&composed), vec![(0, 2, 25)]);
+
+        let cartesian = left.cartesian_product(&right);
+        assert_eq!(cartesian.edge_count(), 2 * 3 + 2 * 3);
+
+        let tensor = left.tensor_product(&right, |a, b| a * b);
+        assert_eq!(
+            sorted_edges(&tensor),
+            vec![
+                ((0, 0), (1, 1), 2),
+                ((0, 0), (1, 1), 8),
+                ((0, 1), (1, 2), 2),
+                ((0, 1), (1, 2), 3),
+                ((0, 1), (1, 2), 8),
+                ((0, 1), (1, 2), 12)
+            ]
+        );
+    }
+}
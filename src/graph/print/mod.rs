@@ -1,9 +1,32 @@
+use std::fmt::Display;
+use std::hash;
+use std::io::{Error, ErrorKind, Write};
 use std::{collections::LinkedList, fs::File};
-use std::io::Write;
 
-use super::Graph;
+use super::{intern::KeyedGraph, Graph};
 
-pub fn print_to_file(graph: &Graph<i64, u64>, file_name: &str) -> std::io::Result<()> {
+/// Separates a vertex's edge list from its payload in graph files.
+pub const DATA_SEPARATOR: char = '|';
+
+pub fn print_to_file<V>(graph: &Graph<i64, u64, V>, file_name: &str) -> std::io::Result<()> {
+    write_graph(graph, file_name, |_| None)
+}
+
+/// Like [`print_to_file`], but appends every vertex payload after [`DATA_SEPARATOR`] and
+/// one space. Backslashes and line breaks in payloads are escaped as `\\`, `\n` and `\r`,
+/// so every vertex stays on one line and the payload reads back exactly.
+pub fn print_to_file_with_data<V: Display>(
+    graph: &Graph<i64, u64, V>,
+    file_name: &str,
+) -> std::io::Result<()> {
+    write_graph(graph, file_name, |data| Some(data.to_string()))
+}
+
+fn write_graph<V>(
+    graph: &Graph<i64, u64, V>,
+    file_name: &str,
+    data_to_string: impl Fn(&V) -> Option<String>,
+) -> std::io::Result<()> {
     let mut file = File::create(file_name)?;
     let mut sorted = Vec::from_iter(graph.content.iter());
 
@@ -11,12 +34,66 @@ pub fn print_to_file(graph: &Graph<i64, u64>, file_name: &str) -> std::io::Resul
 
     sorted.iter().for_each(|(vertex, edge_list)| {
         let vertex_str = vertex_to_string(**vertex);
-        let list_str = edge_list_to_string(*edge_list);
-        write!(&mut file, "{} {}\n", vertex_str, list_str).expect("Couldn't write!");
+        let list_str = edge_list_to_string(edge_list);
+        let data_str = graph
+            .vertex_data(vertex)
+            .and_then(&data_to_string)
+            .map(|data| format!("{} {}", DATA_SEPARATOR, escape_data(&data)))
+            .unwrap_or_default();
+        writeln!(&mut file, "{} {}{}", vertex_str, list_str, data_str).expect("Couldn't write!");
     });
     Ok(())
 }
 
+/// Writes one line per vertex: its key followed by `neighbor weight` pairs, separated by
+/// whitespace. Fails with [`ErrorKind::InvalidInput`] before creating the file if a key
+/// is empty or contains whitespace, since it could not be read back.
+pub fn print_keyed_to_file<Q, V>(
+    graph: &KeyedGraph<Q, u64, V>,
+    file_name: &str,
+) -> std::io::Result<()>
+where
+    Q: Display + hash::Hash + Eq + Clone,
+{
+    let keys = Vec::from_iter(graph.interner().iter().map(|(_, key)| key.to_string()));
+    if let Some(key) = graph
+        .graph()
+        .vertices()
+        .map(|id| &keys[id.index()])
+        .find(|key| key.is_empty() || key.contains(char::is_whitespace))
+    {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!("Vertex key {:?} is empty or contains whitespace", key),
+        ));
+    }
+
+    let mut file = File::create(file_name)?;
+
+    for (id, _) in graph.interner().iter() {
+        let Some(edge_list) = graph.graph().adjacency_list(&id) else {
+            continue;
+        };
+        write!(&mut file, "{}", keys[id.index()])?;
+        for (to, weight) in edge_list {
+            write!(&mut file, " {} {}", keys[to.index()], weight)?;
+        }
+        writeln!(&mut file)?;
+    }
+    Ok(())
+}
+
+fn escape_data(data: &str) -> String {
+    let mut escaped = String::with_capacity(data.len());
+    data.chars().for_each(|ch| match ch {
+        '\\' => escaped.push_str("\\\\"),
+        '\n' => escaped.push_str("\\n"),
+        '\r' => escaped.push_str("\\r"),
+        ch => escaped.push(ch),
+    });
+    escaped
+}
+
 fn vertex_to_string(vert: i64) -> String {
     format!("Vertex {}: ", vert)
 }
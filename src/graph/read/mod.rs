@@ -1,29 +1,60 @@
 use std::{
     collections::{HashMap, LinkedList},
     fs::read_to_string,
+    hash,
+    io::{Error, ErrorKind},
+    str::FromStr,
 };
 
-use super::Graph;
+use super::{intern::KeyedGraph, print::DATA_SEPARATOR, Graph};
 
 pub fn read_from_file(file_name: &str) -> std::io::Result<Graph<i64, u64>> {
+    read_graph(file_name, |_| Ok(None))
+}
+
+/// Reads a graph written by [`print_to_file_with_data`](super::print::print_to_file_with_data),
+/// parsing each vertex payload with `V::from_str` once its escapes are undone.
+pub fn read_from_file_with_data<V: FromStr>(
+    file_name: &str,
+) -> std::io::Result<Graph<i64, u64, V>> {
+    read_graph(file_name, |data| {
+        V::from_str(data)
+            .map(Some)
+            .map_err(|_| Error::new(ErrorKind::InvalidData, format!("Bad vertex data: {}", data)))
+    })
+}
+
+fn read_graph<V>(
+    file_name: &str,
+    parse_data: impl Fn(&str) -> std::io::Result<Option<V>>,
+) -> std::io::Result<Graph<i64, u64, V>> {
     let file_contents = read_to_string(file_name)?;
     let mut graph_content = HashMap::new();
+    let mut vertex_data = HashMap::new();
 
     for (idx, line) in file_contents.lines().enumerate() {
-        let mut main_cursor = find_next_int(line).expect(&format!("Start required! Line {}", idx));
+        let (line, data) = match line.split_once(DATA_SEPARATOR) {
+            Some((edges, data)) => {
+                let data = unescape_data(data.strip_prefix(' ').unwrap_or(data), idx)?;
+                (edges, parse_data(&data)?)
+            }
+            None => (line, None),
+        };
+
+        let mut main_cursor =
+            find_next_int(line).unwrap_or_else(|| panic!("Start required! Line {}", idx));
         let mut edge_list = LinkedList::new();
         let vertex = parse_next_int(main_cursor);
-        loop {
-            match find_next_int(main_cursor) {
-                Some(cursor) => {
-                    let edge_vertex = parse_next_int(cursor);
-                    let cursor = find_next_int(cursor).expect(&format!("Weight required! {}", idx));
-                    let weight = parse_next_int(cursor) as u64;
-                    edge_list.push_front((edge_vertex, weight));
-                    main_cursor = cursor;
-                }
-                None => break,
-            }
+        if let Some(data) = data {
+            vertex_data.insert(vertex, data);
+        }
+        while let Some(cursor) = find_next_int(main_cursor) {
+            let edge_vertex = parse_next_int(cursor);
+            let cursor =
+                find_next_int(cursor).unwrap_or_else(|| panic!("Weight required! {}", idx));
+            let weight = parse_next_int(cursor) as u64;
+            edge_list.push_front((edge_vertex, weight));
+            main_cursor = cursor;
         }
         graph_content.insert(
             vertex,
@@ -31,14 +62,89 @@ pub fn read_from_file(file_name: &str) -> std::io::Result<Graph<i64, u64>> {
         );
     }
 
-    Ok(Graph {
-        content: graph_content,
-    })
+    Ok(Graph::from_parts(graph_content, vertex_data))
+}
+
+/// Reads a graph written by [`print_keyed_to_file`](super::print::print_keyed_to_file),
+/// parsing every vertex key with `Q::from_str`.
+pub fn read_keyed_from_file<Q>(file_name: &str) -> std::io::Result<KeyedGraph<Q, u64>>
+where
+    Q: FromStr + hash::Hash + Eq + Clone,
+{
+    let file_contents = read_to_string(file_name)?;
+    let mut graph = KeyedGraph::<Q, u64>::new();
+    let mut edge_lists = Vec::new();
+
+    for (idx, line) in file_contents.lines().enumerate() {
+        let mut tokens = line.split_whitespace();
+        let Some(vertex) = tokens.next() else {
+            continue;
+        };
+        let vertex = graph
+            .try_insert_vertex(parse_key(vertex)?)
+            .map_err(|_| Error::new(ErrorKind::InvalidData, "Too many vertex keys"))?;
+
+        let mut edge_list = Vec::new();
+        while let Some(to) = tokens.next() {
+            let weight = tokens
+                .next()
+                .and_then(|weight| weight.parse::<u64>().ok())
+                .ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::InvalidData,
+                        format!("Weight required! Line {}", idx),
+                    )
+                })?;
+            edge_list.push((parse_key::<Q>(to)?, weight));
+        }
+        edge_lists.push((vertex, edge_list));
+    }
+
+    for (vertex, edge_list) in edge_lists {
+        let from = graph.key(vertex).unwrap().clone();
+        // Edges are pushed to the front, so insert them backwards to keep the file order.
+        for (to, weight) in edge_list.into_iter().rev() {
+            graph.insert_edge(&from, &to, weight).ok_or_else(|| {
+                Error::new(ErrorKind::InvalidData, "Edge to an undeclared vertex")
+            })?;
+        }
+    }
+
+    Ok(graph)
+}
+
+/// Undoes the escapes of [`print_to_file_with_data`](super::print::print_to_file_with_data).
+fn unescape_data(data: &str, idx: usize) -> std::io::Result<String> {
+    let mut unescaped = String::with_capacity(data.len());
+    let mut chars = data.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            unescaped.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('\\') => unescaped.push('\\'),
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Bad escape in vertex data! Line {}", idx),
+                ))
+            }
+        }
+    }
+    Ok(unescaped)
+}
+
+fn parse_key<Q: FromStr>(key: &str) -> std::io::Result<Q> {
+    Q::from_str(key)
+        .map_err(|_| Error::new(ErrorKind::InvalidData, format!("Bad vertex key: {}", key)))
 }
 
 fn parse_next_int(line: &str) -> i64 {
     line.chars()
-        .take_while(|ch| ch.is_digit(10))
+        .take_while(|ch| ch.is_ascii_digit())
         .fold(None, |acc, ch| {
             ch.to_digit(10).map(|b| acc.unwrap_or(0) * 10 + b)
         })
@@ -48,19 +154,22 @@ fn parse_next_int(line: &str) -> i64 {
 
 fn find_next_int(line: &str) -> Option<&str> {
     line.char_indices()
-        .skip_while(|(_, ch)| ch.is_digit(10))
-        .skip_while(|(_, ch)| !ch.is_digit(10))
-        .nth(0)
+        .skip_while(|(_, ch)| ch.is_ascii_digit())
+        .find(|(_, ch)| ch.is_ascii_digit())
         .map(|(idx, _)| &line[idx..])
 }
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashSet;
+    use std::{collections::HashSet, io::ErrorKind};
 
-    use crate::graph::{print::print_to_file, Graph};
+    use crate::graph::{
+        intern::KeyedGraph,
+        print::{print_keyed_to_file, print_to_file, print_to_file_with_data},
+        Graph,
+    };
 
-    use super::read_from_file;
+    use super::{read_from_file, read_from_file_with_data, read_keyed_from_file};
 
     #[test]
     fn read_from_file_() {
@@ -80,6 +189,99 @@ mod tests {
             HashSet::<i64>::from_iter(same_graph.get_vertices().iter().cloned().cloned())
         );
 
-        assert_eq!(graph.content, same_graph.content);
+        assert_eq!(graph, same_graph);
+    }
+
+    #[test]
+    fn read_from_file_with_data_() {
+        let mut graph = Graph::<i64, u64, String>::with_vertex_data();
+        graph.insert_vertex_with_data(0, "Vilnius | Old Town".to_owned());
+        graph.insert_vertex(1);
+        graph.insert_vertex_with_data(2, "Kaunas 2".to_owned());
+
+        graph.insert_edge(0, 1, 20);
+        graph.insert_edge(2, 0, 40);
+        let file_name = "files/test_data.graph";
+        print_to_file_with_data(&graph, file_name).expect("Couldn't print graph!");
+
+        let same_graph =
+            read_from_file_with_data::<String>(file_name).expect("Couldn't read graph!");
+        assert_eq!(graph, same_graph);
+        assert_eq!(same_graph.vertex_data(&0).unwrap(), "Vilnius | Old Town");
+        assert_eq!(same_graph.vertex_data(&1), None);
+        assert_eq!(same_graph.vertex_data(&2).unwrap(), "Kaunas 2");
+
+        let without_data = read_from_file(file_name).expect("Couldn't read graph!");
+        assert_eq!(graph.content, without_data.content);
+        assert_eq!(without_data.vertex_data(&0), None);
+    }
+
+    #[test]
+    fn read_keyed_from_file_() {
+        let mut graph = KeyedGraph::<String, u64>::new();
+        graph.insert_vertex("a".to_owned());
+        graph.insert_vertex("b".to_owned());
+        graph.insert_vertex("lonely".to_owned());
+
+        graph.insert_edge("a", "b", 20);
+        graph.insert_edge("a", "b", 30);
+        graph.insert_edge("b", "a", 40);
+        let file_name = "files/test_keyed.graph";
+        print_keyed_to_file(&graph, file_name).expect("Couldn't print graph!");
+
+        let same_graph = read_keyed_from_file::<String>(file_name).expect("Couldn't read graph!");
+        assert_eq!(same_graph.vertex_count(), 3);
+        assert!(same_graph.vertex_exists("lonely"));
+        assert_eq!(
+            Vec::from_iter(same_graph.edges_from("a")),
+            Vec::from_iter(graph.edges_from("a"))
+        );
+        assert_eq!(same_graph.edge_data("b", "a"), Some(&40));
+    }
+
+    #[test]
+    fn print_keyed_rejects_whitespace() {
+        for key in ["New York", "two\nlines", ""] {
+            let mut graph = KeyedGraph::<String, u64>::new();
+            graph.insert_vertex("a".to_owned());
+            graph.insert_vertex(key.to_owned());
+            graph.insert_edge("a", key, 5);
+
+            let file_name = "files/test_keyed_whitespace.graph";
+            let _ = std::fs::remove_file(file_name);
+            let error = print_keyed_to_file(&graph, file_name).unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::InvalidInput);
+            assert!(!std::path::Path::new(file_name).exists());
+        }
+    }
+
+    #[test]
+    fn vertex_data_round_trip() {
+        let payloads = [
+            "  leading",
+            "trailing \t ",
+            "two\nlines\r\n",
+            "back\\slash \\n | pipe",
+            "",
+        ];
+        let mut graph = Graph::<i64, u64, String>::with_vertex_data();
+        payloads.iter().enumerate().for_each(|(vertex, data)| {
+            graph.insert_vertex_with_data(vertex as i64, data.to_string());
+        });
+        graph.insert_edge(0, 4, 7);
+        let file_name = "files/test_data_escapes.graph";
+        print_to_file_with_data(&graph, file_name).expect("Couldn't print graph!");
+
+        assert_eq!(
+            std::fs::read_to_string(file_name).unwrap().lines().count(),
+            payloads.len()
+        );
+        let same_graph =
+            read_from_file_with_data::<String>(file_name).expect("Couldn't read graph!");
+        assert_eq!(same_graph, graph);
+
+        std::fs::write(file_name, "Vertex 0:  | bad \\x\n").unwrap();
+        let error = read_from_file_with_data::<String>(file_name).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
     }
 }
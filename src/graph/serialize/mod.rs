@@ -0,0 +1,232 @@
+//! Serde support for [`Graph`], enabled by the `serde` feature.
+//!
+//! A graph is written as a list of vertices and a list of edges, independent of how it is
+//! stored in memory:
+//!
+//! ```json
+//! {
+//!   "vertices": [{ "key": 0, "data": "Vilnius" }, { "key": 1 }],
+//!   "edges": [{ "from": 0, "to": 1, "weight": 20 }]
+//! }
+//! ```
+//!
+//! Vertices are sorted by key and `data` is left out for vertices without a payload. Edges
+//! are grouped by `from` in key order, keeping each vertex's adjacency order, so a graph
+//! reads back with the same edge order it was written with.
+
+use std::{fmt::Debug, hash};
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use super::Graph;
+
+#[derive(Serialize)]
+struct GraphRef<'a, K, T, V> {
+    vertices: Vec<VertexRef<'a, K, V>>,
+    edges: Vec<EdgeRef<'a, K, T>>,
+}
+
+#[derive(Serialize)]
+struct VertexRef<'a, K, V> {
+    key: &'a K,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<&'a V>,
+}
+
+#[derive(Serialize)]
+struct EdgeRef<'a, K, T> {
+    from: &'a K,
+    to: &'a K,
+    weight: &'a T,
+}
+
+#[derive(Deserialize)]
+struct GraphRepr<K, T, V> {
+    vertices: Vec<VertexRepr<K, V>>,
+    edges: Vec<EdgeRepr<K, T>>,
+}
+
+#[derive(Deserialize)]
+struct VertexRepr<K, V> {
+    key: K,
+    #[serde(default = "Option::default")]
+    data: Option<V>,
+}
+
+#[derive(Deserialize)]
+struct EdgeRepr<K, T> {
+    from: K,
+    to: K,
+    weight: T,
+}
+
+impl<K, T, V> Serialize for Graph<K, T, V>
+where
+    K: hash::Hash + Eq + Copy + Debug + Ord + Serialize,
+    T: Debug + Clone + Serialize,
+    V: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut keys = Vec::from_iter(self.vertices());
+        keys.sort();
+
+        GraphRef {
+            vertices: keys
+                .iter()
+                .map(|key| VertexRef {
+                    key: *key,
+                    data: self.vertex_data(key),
+                })
+                .collect(),
+            edges: keys
+                .iter()
+                .flat_map(|from| {
+                    let from = *from;
+                    self.edges_from(from)
+                        .map(move |(to, weight)| EdgeRef { from, to, weight })
+                })
+                .collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, K, T, V> Deserialize<'de> for Graph<K, T, V>
+where
+    K: hash::Hash + Eq + Copy + Debug + Ord + Deserialize<'de>,
+    T: Debug + Clone + Deserialize<'de>,
+    V: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = GraphRepr::<K, T, V>::deserialize(deserializer)?;
+        let mut graph = Graph::with_vertex_data();
+
+        for vertex in repr.vertices {
+            if graph.vertex_exists(&vertex.key) {
+                return Err(de::Error::custom(format!(
+                    "duplicate vertex {:?}",
+                    vertex.key
+                )));
+            }
+
+            match vertex.data {
+                Some(data) => graph.insert_vertex_with_data(vertex.key, data),
+                None => graph.insert_vertex(vertex.key),
+            };
+        }
+
+        for edge in repr.edges.into_iter().rev() {
+            let (from, to) = (edge.from, edge.to);
+            graph.insert_edge(from, to, edge.weight).ok_or_else(|| {
+                de::Error::custom(format!(
+                    "edge {:?} -> {:?} references an unknown vertex",
+                    from, to
+                ))
+            })?;
+        }
+
+        Ok(graph)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{
+        djikstra::{
+            djikstra,
+            node::{tuples, Node},
+        },
+        generate::{GenerationParameters, GraphGenerationError},
+        Graph,
+    };
+
+    #[test]
+    fn json_shape() {
+        let mut graph = Graph::<i64, u64, String>::with_vertex_data();
+        graph.insert_vertex_with_data(0, "Vilnius".to_owned());
+        graph.insert_vertex(1);
+        graph.insert_edge(0, 1, 20);
+        graph.insert_edge(1, 0, 30);
+
+        assert_eq!(
+            serde_json::to_string(&graph).unwrap(),
+            r#"{"vertices":[{"key":0,"data":"Vilnius"},{"key":1}],"edges":[{"from":0,"to":1,"weight":20},{"from":1,"to":0,"weight":30}]}"#
+        );
+
+        let json = r#"{"vertices":[{"key":1},{"key":0,"data":"Vilnius"}],"edges":[{"from":0,"to":1,"weight":20},{"from":0,"to":1,"weight":25},{"from":1,"to":0,"weight":30}]}"#;
+        let read: Graph<i64, u64, String> = serde_json::from_str(json).unwrap();
+        assert_eq!(read.vertex_data(&0).map(String::as_str), Some("Vilnius"));
+        assert_eq!(read.vertex_data(&1), None);
+        assert_eq!(
+            Vec::from_iter(read.edges_from(&0).map(|(to, weight)| (*to, *weight))),
+            [(1, 20), (1, 25)]
+        );
+        assert_eq!(
+            serde_json::to_string(&read).unwrap(),
+            r#"{"vertices":[{"key":0,"data":"Vilnius"},{"key":1}],"edges":[{"from":0,"to":1,"weight":20},{"from":0,"to":1,"weight":25},{"from":1,"to":0,"weight":30}]}"#
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut graph = Graph::<i64, u64>::new();
+        graph.insert_vertex(0);
+        graph.insert_vertex(1);
+        graph.insert_vertex(2);
+        graph.insert_edge(0, 1, 20);
+        graph.insert_edge(0, 2, 40);
+        graph.insert_edge(1, 2, 60);
+
+        let json = serde_json::to_string(&graph).unwrap();
+        let same_graph: Graph<i64, u64> = serde_json::from_str(&json).unwrap();
+        assert_eq!(graph.content, same_graph.content);
+        assert_eq!(same_graph.in_degree(&2), 2);
+    }
+
+    #[test]
+    fn invalid_graphs() {
+        let unknown = r#"{"vertices":[{"key":0}],"edges":[{"from":0,"to":1,"weight":1}]}"#;
+        let error = serde_json::from_str::<Graph<i64, u64>>(unknown).unwrap_err();
+        assert!(error.to_string().contains("unknown vertex"));
+
+        let duplicate = r#"{"vertices":[{"key":0},{"key":0}],"edges":[]}"#;
+        assert!(serde_json::from_str::<Graph<i64, u64>>(duplicate).is_err());
+    }
+
+    #[test]
+    fn other_types() {
+        let mut graph = Graph::<i64, u64>::new();
+        graph.insert_vertex(0);
+        graph.insert_vertex(1);
+        graph.insert_edge(0, 1, 5);
+
+        let results = djikstra(&graph, 0);
+        let json = serde_json::to_string(&results).unwrap();
+        assert_eq!(
+            json,
+            r#"[{"vertex":0,"distance":0,"prev":null},{"vertex":1,"distance":5,"prev":0}]"#
+        );
+        let nodes: Vec<Node<i64, u64>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(tuples(nodes), tuples(results));
+
+        let parameters = GenerationParameters {
+            vertex_count: 10,
+            neighbor_min: 1,
+            neighbor_max: 3,
+        };
+        let json = serde_json::to_string(&parameters).unwrap();
+        assert_eq!(
+            json,
+            r#"{"vertex_count":10,"neighbor_min":1,"neighbor_max":3}"#
+        );
+        let parsed: GenerationParameters = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.neighbor_max, 3);
+
+        let error = GraphGenerationError::TooManyEdges;
+        let json = serde_json::to_string(&error).unwrap();
+        assert_eq!(
+            serde_json::from_str::<GraphGenerationError>(&json).unwrap(),
+            error
+        );
+    }
+}
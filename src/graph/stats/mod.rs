@@ -0,0 +1,343 @@
+//! Summary statistics of a graph, mainly for checking what a generator produced.
+
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt::{self, Debug, Display},
+    hash,
+};
+
+use super::{generate::GenerationParameters, traits::Weight, Graph};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DegreeStats {
+    pub min: usize,
+    pub max: usize,
+    pub mean: f64,
+    /// Number of vertices with each degree.
+    pub histogram: BTreeMap<usize, usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeightStats<T> {
+    pub min: T,
+    pub max: T,
+    pub mean: f64,
+}
+
+/// Counts and distributions describing a graph, as computed by [`Graph::stats`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphStats<T> {
+    pub vertex_count: usize,
+    pub edge_count: usize,
+    pub out_degree: DegreeStats,
+    pub in_degree: DegreeStats,
+    /// Edges divided by the number of ordered pairs of distinct vertices.
+    pub density: f64,
+    /// `None` for a graph without edges.
+    pub weights: Option<WeightStats<T>>,
+    pub self_loops: usize,
+    /// Edges beyond the first between the same ordered pair of vertices.
+    pub parallel_edges: usize,
+    /// Sizes of the weakly connected components, largest first.
+    pub component_sizes: Vec<usize>,
+    /// Whether every edge `a -> b` has a matching `b -> a` edge with the same value.
+    pub symmetric: bool,
+}
+
+/// A way in which a graph does not look like the output of
+/// [`generate_undirected`](super::generate::generate_undirected).
+#[derive(Debug, PartialEq)]
+pub enum GenerationMismatch {
+    VertexCount { expected: i64, actual: usize },
+    DegreeBelowMin { min: usize },
+    DegreeAboveMax { max: usize },
+    SelfLoops(usize),
+    ParallelEdges(usize),
+    NotSymmetric,
+}
+
+impl<K, T, V> Graph<K, T, V>
+where
+    K: hash::Hash + Eq + Copy + Debug + Ord,
+    T: Debug + Clone,
+{
+    pub fn stats(&self) -> GraphStats<T>
+    where
+        T: Weight,
+    {
+        let vertex_count = self.vertex_count();
+        let edge_count = self.edge_count();
+        let pairs = vertex_count * vertex_count.saturating_sub(1);
+
+        let mut self_loops = 0;
+        let mut pair_counts = HashMap::new();
+        self.edges().for_each(|(from, to, _)| {
+            if from == to {
+                self_loops += 1;
+            }
+            *pair_counts.entry((from, to)).or_insert(0) += 1;
+        });
+
+        GraphStats {
+            vertex_count,
+            edge_count,
+            out_degree: DegreeStats::new(self.vertices().map(|vertex| self.out_degree(vertex))),
+            in_degree: DegreeStats::new(self.vertices().map(|vertex| self.in_degree(vertex))),
+            density: match pairs {
+                0 => 0.0,
+                pairs => edge_count as f64 / pairs as f64,
+            },
+            weights: WeightStats::new(self.edges().map(|(_, _, value)| *value)),
+            self_loops,
+            parallel_edges: pair_counts.values().map(|count| count - 1).sum(),
+            component_sizes: self.component_sizes(),
+            symmetric: self.edges().all(|(from, to, value)| {
+                self.edges_from(to)
+                    .any(|(back, back_value)| back == from && back_value == value)
+            }),
+        }
+    }
+
+    fn component_sizes(&self) -> Vec<usize> {
+        let mut seen = HashSet::new();
+        let mut sizes = Vec::new();
+
+        for vertex in self.vertices() {
+            if !seen.insert(*vertex) {
+                continue;
+            }
+
+            let mut stack = vec![*vertex];
+            let mut size = 0;
+            while let Some(current) = stack.pop() {
+                size += 1;
+                let outgoing = self.edges_from(&current).map(|(to, _)| to);
+                let incoming = self
+                    .in_edges(&current)
+                    .unwrap()
+                    .iter()
+                    .map(|(from, _)| from);
+                outgoing.chain(incoming).for_each(|neighbor| {
+                    if seen.insert(*neighbor) {
+                        stack.push(*neighbor);
+                    }
+                });
+            }
+            sizes.push(size);
+        }
+
+        sizes.sort_by(|a, b| b.cmp(a));
+        sizes
+    }
+}
+
+impl DegreeStats {
+    fn new(degrees: impl Iterator<Item = usize>) -> DegreeStats {
+        let mut histogram = BTreeMap::new();
+        degrees.for_each(|degree| *histogram.entry(degree).or_insert(0) += 1);
+
+        let count: usize = histogram.values().sum();
+        let total: usize = histogram.iter().map(|(degree, count)| degree * count).sum();
+
+        DegreeStats {
+            min: histogram.keys().next().copied().unwrap_or(0),
+            max: histogram.keys().next_back().copied().unwrap_or(0),
+            mean: match count {
+                0 => 0.0,
+                count => total as f64 / count as f64,
+            },
+            histogram,
+        }
+    }
+}
+
+impl<T: Weight> WeightStats<T> {
+    fn new(weights: impl Iterator<Item = T>) -> Option<WeightStats<T>> {
+        let mut count = 0;
+        let mut sum = 0.0;
+        let mut min_max: Option<(T, T)> = None;
+
+        weights.for_each(|weight| {
+            count += 1;
+            sum += weight.to_f64();
+            min_max = Some(match min_max {
+                Some((min, max)) => (min.min(weight), max.max(weight)),
+                None => (weight, weight),
+            });
+        });
+
+        min_max.map(|(min, max)| WeightStats {
+            min,
+            max,
+            mean: sum / count as f64,
+        })
+    }
+}
+
+impl<T> GraphStats<T> {
+    /// Everything that contradicts `parameters`, assuming the graph came from
+    /// [`generate_undirected`](super::generate::generate_undirected).
+    pub fn mismatches(&self, parameters: &GenerationParameters) -> Vec<GenerationMismatch> {
+        let mut mismatches = Vec::new();
+
+        if self.vertex_count as i64 != parameters.vertex_count {
+            mismatches.push(GenerationMismatch::VertexCount {
+                expected: parameters.vertex_count,
+                actual: self.vertex_count,
+            });
+        }
+        if self.vertex_count > 0 && (self.out_degree.min as i64) < parameters.neighbor_min {
+            mismatches.push(GenerationMismatch::DegreeBelowMin {
+                min: self.out_degree.min,
+            });
+        }
+        if self.vertex_count > 0 && self.out_degree.max as i64 >= parameters.neighbor_max {
+            mismatches.push(GenerationMismatch::DegreeAboveMax {
+                max: self.out_degree.max,
+            });
+        }
+        if self.self_loops > 0 {
+            mismatches.push(GenerationMismatch::SelfLoops(self.self_loops));
+        }
+        if self.parallel_edges > 0 {
+            mismatches.push(GenerationMismatch::ParallelEdges(self.parallel_edges));
+        }
+        if !self.symmetric {
+            mismatches.push(GenerationMismatch::NotSymmetric);
+        }
+
+        mismatches
+    }
+}
+
+impl Display for DegreeStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "min {}, max {}, mean {:.2}",
+            self.min, self.max, self.mean
+        )?;
+        self.histogram
+            .iter()
+            .try_for_each(|(degree, count)| writeln!(f, "  {:>4}: {}", degree, count))
+    }
+}
+
+impl<T: Display> Display for GraphStats<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "vertices: {}", self.vertex_count)?;
+        writeln!(
+            f,
+            "edges: {} (density {:.3})",
+            self.edge_count, self.density
+        )?;
+        write!(f, "out-degree: {}", self.out_degree)?;
+        write!(f, "in-degree: {}", self.in_degree)?;
+        match &self.weights {
+            Some(weights) => writeln!(
+                f,
+                "weights: min {}, max {}, mean {:.2}",
+                weights.min, weights.max, weights.mean
+            )?,
+            None => writeln!(f, "weights: none")?,
+        }
+        writeln!(f, "self-loops: {}", self.self_loops)?;
+        writeln!(f, "parallel edges: {}", self.parallel_edges)?;
+        writeln!(
+            f,
+            "components: {} (sizes {:?})",
+            self.component_sizes.len(),
+            self.component_sizes
+        )?;
+        writeln!(
+            f,
+            "symmetric: {}",
+            if self.symmetric { "yes" } else { "no" }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::GenerationMismatch;
+    use crate::graph::{
+        generate::{generate_undirected, GenerationParameters},
+        Graph,
+    };
+
+    #[test]
+    fn stats() {
+        let mut graph = Graph::new();
+        (0..5).for_each(|vertex| {
+            graph.insert_vertex(vertex);
+        });
+        graph.insert_edge(0, 1, 10);
+        graph.insert_edge(1, 0, 10);
+        graph.insert_edge(1, 2, 20);
+        graph.insert_edge(1, 2, 30);
+        graph.insert_edge(2, 2, 5);
+        graph.insert_edge(3, 4, 15);
+
+        let stats = graph.stats();
+        assert_eq!(stats.vertex_count, 5);
+        assert_eq!(stats.edge_count, 6);
+        assert_eq!(stats.density, 6.0 / 20.0);
+        assert_eq!(stats.out_degree.min, 0);
+        assert_eq!(stats.out_degree.max, 3);
+        assert_eq!(stats.out_degree.mean, 1.2);
+        assert_eq!(
+            stats.out_degree.histogram,
+            BTreeMap::from([(0, 1), (1, 3), (3, 1)])
+        );
+        assert_eq!(stats.in_degree.max, 3);
+
+        let weights = stats.weights.as_ref().unwrap();
+        assert_eq!((weights.min, weights.max), (5, 30));
+        assert_eq!(weights.mean, 15.0);
+
+        assert_eq!(stats.self_loops, 1);
+        assert_eq!(stats.parallel_edges, 1);
+        assert_eq!(stats.component_sizes, [3, 2]);
+        assert!(!stats.symmetric);
+
+        let report = stats.to_string();
+        assert!(report.contains("edges: 6 (density 0.300)"));
+        assert!(report.contains("components: 2 (sizes [3, 2])"));
+    }
+
+    #[test]
+    fn empty() {
+        let stats = Graph::<i64, u64>::new().stats();
+        assert_eq!(stats.edge_count, 0);
+        assert_eq!(stats.density, 0.0);
+        assert_eq!(stats.weights, None);
+        assert!(stats.symmetric);
+        assert!(stats.component_sizes.is_empty());
+    }
+
+    #[test]
+    fn generated_graph_matches_parameters() {
+        let parameters = GenerationParameters {
+            vertex_count: 30,
+            neighbor_min: 1,
+            neighbor_max: 4,
+        };
+        let stats = generate_undirected(&parameters).unwrap().stats();
+        assert_eq!(stats.mismatches(&parameters), []);
+        assert!(stats.weights.as_ref().unwrap().max < 100);
+
+        let parameters = GenerationParameters {
+            vertex_count: 31,
+            neighbor_min: 4,
+            neighbor_max: 5,
+        };
+        assert!(stats
+            .mismatches(&parameters)
+            .contains(&GenerationMismatch::VertexCount {
+                expected: 31,
+                actual: 30
+            }));
+    }
+}
@@ -0,0 +1,219 @@
+use std::hash;
+
+/// Common types shared by every graph representation.
+pub trait GraphBase {
+    type Key: hash::Hash + Eq + Copy;
+    type Weight;
+}
+
+/// Graphs whose vertices can be enumerated up front.
+pub trait VertexSet: GraphBase {
+    type VertexKeys<'a>: Iterator<Item = Self::Key>
+    where
+        Self: 'a;
+
+    fn vertex_keys(&self) -> Self::VertexKeys<'_>;
+
+    fn has_vertex(&self, vertex: &Self::Key) -> bool;
+
+    fn vertex_count(&self) -> usize {
+        self.vertex_keys().count()
+    }
+}
+
+/// Graphs that can list the outgoing edges of a vertex.
+pub trait Neighbors: GraphBase {
+    type Successors<'a>: Iterator<Item = (Self::Key, Self::Weight)>
+    where
+        Self: 'a;
+
+    /// Outgoing edges of `vertex` as `(to, weight)` pairs. Unknown vertices have none.
+    fn successors(&self, vertex: &Self::Key) -> Self::Successors<'_>;
+}
+
+/// Graphs that can look up a single edge directly.
+pub trait EdgeWeight: GraphBase {
+    /// Weight of a `from -> to` edge. With parallel edges any one of them may be returned.
+    fn edge_weight(&self, from: &Self::Key, to: &Self::Key) -> Option<Self::Weight>;
+}
+
+/// Graphs whose vertices map onto dense indices `0..index_bound()`, so algorithms
+/// can keep per-vertex state in plain vectors.
+pub trait IndexedGraph: GraphBase {
+    fn index_bound(&self) -> usize;
+
+    fn vertex_index(&self, vertex: &Self::Key) -> Option<usize>;
+
+    fn vertex_at(&self, index: usize) -> Option<Self::Key>;
+}
+
+/// Edge weights shortest-path algorithms can add up and compare.
+pub trait Weight: Copy + Ord {
+    fn zero() -> Self;
+
+    /// Distance of a vertex that cannot be reached.
+    fn infinity() -> Self;
+
+    /// Sum of two weights, saturating at [`Weight::infinity`].
+    fn plus(self, other: Self) -> Self;
+
+    /// Approximate value, for statistics.
+    fn to_f64(self) -> f64;
+
+    /// The value as a `u64`, for unsigned types no wider than that. Integer queues
+    /// such as [`RadixHeap`](crate::heap::radix::RadixHeap) rely on it.
+    fn to_unsigned(self) -> Option<u64>;
+}
+
+macro_rules! impl_weight {
+    ($unsigned:expr; $($t:ty),*) => {
+        $(impl Weight for $t {
+            fn zero() -> Self {
+                0
+            }
+
+            fn infinity() -> Self {
+                <$t>::MAX
+            }
+
+            fn plus(self, other: Self) -> Self {
+                self.saturating_add(other)
+            }
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+
+            fn to_unsigned(self) -> Option<u64> {
+                $unsigned.then_some(self as u64)
+            }
+        })*
+    };
+}
+
+impl_weight!(true; u8, u16, u32, u64, usize);
+impl_weight!(false; u128, i8, i16, i32, i64, i128, isize);
+
+/// Unsigned weights no wider than `u64`, which integer queues such as
+/// [`RadixHeap`](crate::heap::radix::RadixHeap) can use as keys directly. Signed
+/// distances are rejected at compile time:
+///
+/// ```compile_fail
+/// use rusty_graph::{
+///     graph::djikstra::node::Node,
+///     heap::{queue::PriorityQueue, radix::RadixHeap},
+/// };
+///
+/// let mut queue = RadixHeap::new();
+/// queue.push(Node { vertex: 0, distance: -1i64, prev: None });
+/// ```
+pub trait UnsignedWeight: Weight {
+    fn to_u64(self) -> u64;
+}
+
+macro_rules! impl_unsigned_weight {
+    ($($t:ty),*) => {
+        $(impl UnsignedWeight for $t {
+            fn to_u64(self) -> u64 {
+                self as u64
+            }
+        })*
+    };
+}
+
+impl_unsigned_weight!(u8, u16, u32, u64, usize);
+
+#[cfg(test)]
+mod tests {
+    use std::iter;
+
+    use super::{GraphBase, IndexedGraph, Neighbors, VertexSet};
+    use crate::graph::djikstra::djikstra;
+
+    /// A 4-connected grid without any stored adjacency. Moving into row `y` costs `y + 1`.
+    struct Grid {
+        width: i32,
+        height: i32,
+    }
+
+    impl GraphBase for Grid {
+        type Key = (i32, i32);
+        type Weight = u32;
+    }
+
+    impl VertexSet for Grid {
+        type VertexKeys<'a> = Box<dyn Iterator<Item = (i32, i32)> + 'a>;
+
+        fn vertex_keys(&self) -> Self::VertexKeys<'_> {
+            Box::new((0..self.height).flat_map(move |y| (0..self.width).map(move |x| (x, y))))
+        }
+
+        fn has_vertex(&self, (x, y): &(i32, i32)) -> bool {
+            (0..self.width).contains(x) && (0..self.height).contains(y)
+        }
+    }
+
+    impl Neighbors for Grid {
+        type Successors<'a> = Box<dyn Iterator<Item = ((i32, i32), u32)> + 'a>;
+
+        fn successors(&self, vertex: &(i32, i32)) -> Self::Successors<'_> {
+            if !self.has_vertex(vertex) {
+                return Box::new(iter::empty());
+            }
+
+            let (x, y) = *vertex;
+            Box::new(
+                [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+                    .into_iter()
+                    .filter(|neighbor| self.has_vertex(neighbor))
+                    .map(|(x, y)| ((x, y), y as u32 + 1)),
+            )
+        }
+    }
+
+    impl IndexedGraph for Grid {
+        fn index_bound(&self) -> usize {
+            (self.width * self.height) as usize
+        }
+
+        fn vertex_index(&self, vertex: &(i32, i32)) -> Option<usize> {
+            self.has_vertex(vertex)
+                .then(|| (vertex.1 * self.width + vertex.0) as usize)
+        }
+
+        fn vertex_at(&self, index: usize) -> Option<(i32, i32)> {
+            let index = index as i32;
+            let vertex = (index % self.width, index / self.width);
+            self.has_vertex(&vertex).then_some(vertex)
+        }
+    }
+
+    #[test]
+    fn djikstra_on_user_graph() {
+        let grid = Grid {
+            width: 3,
+            height: 3,
+        };
+
+        let results = djikstra(&grid, (0, 0));
+        assert_eq!(results.len(), 9);
+
+        let corner = results.iter().find(|node| node.vertex == (2, 2)).unwrap();
+        assert_eq!(corner.distance, 1 + 1 + 2 + 3);
+        assert_eq!(grid.vertex_count(), 9);
+    }
+
+    #[test]
+    fn indexed_round_trip() {
+        let grid = Grid {
+            width: 4,
+            height: 2,
+        };
+
+        (0..grid.index_bound()).for_each(|index| {
+            let vertex = grid.vertex_at(index).unwrap();
+            assert_eq!(grid.vertex_index(&vertex), Some(index));
+        });
+        assert_eq!(grid.vertex_index(&(4, 0)), None);
+    }
+}
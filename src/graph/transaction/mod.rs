@@ -0,0 +1,774 @@
+//! Batches of graph mutations that apply all at once or not at all.
+//!
+//! A [`Transaction`] is checked against the current graph before anything is touched, so
+//! a failing change leaves the graph exactly as it was. Applying a batch yields an
+//! [`Undo`] that reverts it as long as no vertex or edge has changed since; an
+//! [`UndoLog`] keeps the most recent ones.
+
+use std::{
+    collections::{HashMap, HashSet, LinkedList, VecDeque},
+    fmt::Debug,
+    hash,
+};
+
+use super::{observe::GraphEvent, Graph};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change<K, T, V = ()> {
+    /// Adds a vertex that must not exist yet.
+    InsertVertex(K),
+    InsertVertexWithData(K, V),
+    SetVertexData(K, V),
+    RemoveVertex(K),
+    InsertEdge(K, K, T),
+    /// Replaces the value of the most recently inserted edge between the pair.
+    SetEdgeData(K, K, T),
+    RemoveEdge(K, K),
+}
+
+/// Why a transaction was rejected. `index` is the position of the offending change.
+#[derive(Debug, PartialEq)]
+pub enum TransactionError<K> {
+    VertexExists { index: usize, vertex: K },
+    MissingVertex { index: usize, vertex: K },
+    MissingEdge { index: usize, from: K, to: K },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transaction<K, T, V = ()> {
+    changes: Vec<Change<K, T, V>>,
+}
+
+impl<K, T, V> Default for Transaction<K, T, V> {
+    fn default() -> Self {
+        Transaction {
+            changes: Vec::new(),
+        }
+    }
+}
+
+impl<K, T, V> Transaction<K, T, V> {
+    pub fn new() -> Transaction<K, T, V> {
+        Transaction::default()
+    }
+
+    pub fn push(&mut self, change: Change<K, T, V>) -> &mut Self {
+        self.changes.push(change);
+        self
+    }
+
+    pub fn insert_vertex(&mut self, vertex: K) -> &mut Self {
+        self.push(Change::InsertVertex(vertex))
+    }
+
+    pub fn insert_vertex_with_data(&mut self, vertex: K, data: V) -> &mut Self {
+        self.push(Change::InsertVertexWithData(vertex, data))
+    }
+
+    pub fn set_vertex_data(&mut self, vertex: K, data: V) -> &mut Self {
+        self.push(Change::SetVertexData(vertex, data))
+    }
+
+    pub fn remove_vertex(&mut self, vertex: K) -> &mut Self {
+        self.push(Change::RemoveVertex(vertex))
+    }
+
+    pub fn insert_edge(&mut self, from: K, to: K, value: T) -> &mut Self {
+        self.push(Change::InsertEdge(from, to, value))
+    }
+
+    pub fn set_edge_data(&mut self, from: K, to: K, value: T) -> &mut Self {
+        self.push(Change::SetEdgeData(from, to, value))
+    }
+
+    pub fn remove_edge(&mut self, from: K, to: K) -> &mut Self {
+        self.push(Change::RemoveEdge(from, to))
+    }
+
+    pub fn changes(&self) -> &[Change<K, T, V>] {
+        &self.changes
+    }
+
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl<K, T, V> Extend<Change<K, T, V>> for Transaction<K, T, V> {
+    fn extend<I: IntoIterator<Item = Change<K, T, V>>>(&mut self, changes: I) {
+        self.changes.extend(changes);
+    }
+}
+
+impl<K, T, V> FromIterator<Change<K, T, V>> for Transaction<K, T, V> {
+    fn from_iter<I: IntoIterator<Item = Change<K, T, V>>>(changes: I) -> Self {
+        Transaction {
+            changes: Vec::from_iter(changes),
+        }
+    }
+}
+
+/// Reverts one applied transaction. Edge values, payloads and the order of every
+/// adjacency list come back exactly, so traversals visit edges as they did before.
+#[derive(Debug)]
+pub struct Undo<K, T, V = ()> {
+    steps: Vec<Inverse<K, T, V>>,
+    /// Revisions of the graph before and after the transaction.
+    before: u64,
+    after: u64,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum UndoError {
+    /// A vertex or edge changed after the transaction was applied, so its positions
+    /// no longer line up. The graph is left untouched.
+    GraphChanged,
+}
+
+/// Entries a removal took out of one list of another vertex, as `(position, edge)` pairs
+/// in ascending order of position.
+type Removed<K, T> = Vec<(usize, (K, T))>;
+
+#[derive(Debug)]
+enum Inverse<K, T, V> {
+    RemoveVertex(K),
+    RestoreVertex {
+        vertex: K,
+        data: Option<V>,
+        outgoing: LinkedList<(K, T)>,
+        incoming: LinkedList<(K, T)>,
+        /// Edges into `vertex`, taken out of the adjacency lists of their sources.
+        sources: Vec<(K, Removed<K, T>)>,
+        /// Edges out of `vertex`, taken out of the incoming lists of their targets.
+        targets: Vec<(K, Removed<K, T>)>,
+    },
+    RestoreData(K, Option<V>),
+    RemoveEdge(K, K),
+    InsertEdge {
+        from: K,
+        to: K,
+        value: T,
+        outgoing_position: usize,
+        incoming_position: usize,
+    },
+    SetEdgeData(K, K, T),
+}
+
+impl<K, T, V> Graph<K, T, V>
+where
+    K: hash::Hash + Eq + Copy + Debug + Ord,
+    T: Debug + Clone,
+{
+    /// Checks that every change of `transaction` would succeed when applied in order.
+    pub fn validate(&self, transaction: &Transaction<K, T, V>) -> Result<(), TransactionError<K>> {
+        let mut shadow = Shadow::new(self);
+
+        transaction
+            .changes
+            .iter()
+            .enumerate()
+            .try_for_each(|(index, change)| shadow.check(index, change))
+    }
+
+    /// Applies every change of `transaction`, or none of them if any would fail.
+    pub fn apply(
+        &mut self,
+        transaction: Transaction<K, T, V>,
+    ) -> Result<Undo<K, T, V>, TransactionError<K>> {
+        self.validate(&transaction)?;
+
+        let before = self.revision;
+        let steps = transaction
+            .changes
+            .into_iter()
+            .map(|change| self.apply_change(change))
+            .collect();
+
+        Ok(Undo {
+            steps,
+            before,
+            after: self.revision,
+        })
+    }
+
+    /// Reverts an applied transaction. Refuses, without changing anything, if a vertex
+    /// or edge has changed since; payload changes made in between are overwritten.
+    pub fn undo(&mut self, undo: Undo<K, T, V>) -> Result<(), UndoError> {
+        if !self.can_undo(&undo) {
+            return Err(UndoError::GraphChanged);
+        }
+
+        undo.steps
+            .into_iter()
+            .rev()
+            .for_each(|step| self.revert(step));
+        self.revision = undo.before;
+        Ok(())
+    }
+
+    pub fn can_undo(&self, undo: &Undo<K, T, V>) -> bool {
+        self.revision == undo.after
+    }
+
+    fn apply_change(&mut self, change: Change<K, T, V>) -> Inverse<K, T, V> {
+        match change {
+            Change::InsertVertex(vertex) => {
+                self.insert_vertex(vertex);
+                Inverse::RemoveVertex(vertex)
+            }
+            Change::InsertVertexWithData(vertex, data) => {
+                self.insert_vertex_with_data(vertex, data);
+                Inverse::RemoveVertex(vertex)
+            }
+            Change::SetVertexData(vertex, data) => {
+                let previous = self.take_vertex_data(&vertex);
+                self.set_vertex_data(&vertex, data);
+                Inverse::RestoreData(vertex, previous)
+            }
+            Change::RemoveVertex(vertex) => {
+                let incoming = self.incoming[&vertex].clone();
+                let sources = neighbors_of(&incoming, &vertex)
+                    .map(|from| (from, positions(&self.content[&from], &vertex)))
+                    .collect();
+                let targets = neighbors_of(&self.content[&vertex], &vertex)
+                    .map(|to| (to, positions(&self.incoming[&to], &vertex)))
+                    .collect();
+                let data = self.take_vertex_data(&vertex);
+                let outgoing = self.remove_vertex(&vertex).unwrap();
+
+                Inverse::RestoreVertex {
+                    vertex,
+                    data,
+                    outgoing,
+                    incoming,
+                    sources,
+                    targets,
+                }
+            }
+            Change::InsertEdge(from, to, value) => {
+                self.insert_edge(from, to, value);
+                Inverse::RemoveEdge(from, to)
+            }
+            Change::SetEdgeData(from, to, value) => {
+                let previous = self.set_edge_data(&from, &to, value).unwrap();
+                Inverse::SetEdgeData(from, to, previous)
+            }
+            Change::RemoveEdge(from, to) => {
+                let outgoing_position = self.content[&from]
+                    .iter()
+                    .position(|(other, _)| *other == to)
+                    .unwrap();
+                let incoming_position = self.incoming[&to]
+                    .iter()
+                    .position(|(other, _)| *other == from)
+                    .unwrap();
+                let value = self.remove_edge(&from, &to).unwrap();
+
+                Inverse::InsertEdge {
+                    from,
+                    to,
+                    value,
+                    outgoing_position,
+                    incoming_position,
+                }
+            }
+        }
+    }
+
+    fn revert(&mut self, step: Inverse<K, T, V>) {
+        match step {
+            Inverse::RemoveVertex(vertex) => {
+                self.remove_vertex(&vertex);
+            }
+            Inverse::RestoreVertex {
+                vertex,
+                data,
+                outgoing,
+                incoming,
+                sources,
+                targets,
+            } => {
+                self.insert_vertex(vertex);
+                if let Some(data) = data {
+                    self.set_vertex_data(&vertex, data);
+                }
+
+                // Later positions were counted with the earlier entries in place, so
+                // putting entries back in ascending order lands each one where it was.
+                sources.into_iter().for_each(|(from, removed)| {
+                    let list = self.content.get_mut(&from).unwrap();
+                    removed
+                        .into_iter()
+                        .for_each(|(position, edge)| insert_at(list, position, edge));
+                });
+                targets.into_iter().for_each(|(to, removed)| {
+                    let list = self.incoming.get_mut(&to).unwrap();
+                    removed
+                        .into_iter()
+                        .for_each(|(position, edge)| insert_at(list, position, edge));
+                });
+
+                outgoing.iter().for_each(|(to, value)| {
+                    self.observers.emit(GraphEvent::EdgeAdded {
+                        from: vertex,
+                        to: *to,
+                        value,
+                    });
+                });
+                incoming
+                    .iter()
+                    .filter(|(from, _)| *from != vertex)
+                    .for_each(|(from, value)| {
+                        self.observers.emit(GraphEvent::EdgeAdded {
+                            from: *from,
+                            to: vertex,
+                            value,
+                        });
+                    });
+                self.content.insert(vertex, outgoing);
+                self.incoming.insert(vertex, incoming);
+            }
+            Inverse::RestoreData(vertex, data) => {
+                self.take_vertex_data(&vertex);
+                if let Some(data) = data {
+                    self.set_vertex_data(&vertex, data);
+                }
+            }
+            Inverse::RemoveEdge(from, to) => {
+                self.remove_edge(&from, &to);
+            }
+            Inverse::InsertEdge {
+                from,
+                to,
+                value,
+                outgoing_position,
+                incoming_position,
+            } => {
+                insert_at(
+                    self.incoming.get_mut(&to).unwrap(),
+                    incoming_position,
+                    (from, value.clone()),
+                );
+                self.observers.emit(GraphEvent::EdgeAdded {
+                    from,
+                    to,
+                    value: &value,
+                });
+                insert_at(
+                    self.content.get_mut(&from).unwrap(),
+                    outgoing_position,
+                    (to, value),
+                );
+            }
+            Inverse::SetEdgeData(from, to, value) => {
+                self.set_edge_data(&from, &to, value);
+            }
+        }
+    }
+}
+
+/// Every vertex other than `vertex` appearing in `list`, once each.
+fn neighbors_of<'a, K: hash::Hash + Eq + Copy, T>(
+    list: &'a LinkedList<(K, T)>,
+    vertex: &'a K,
+) -> impl Iterator<Item = K> + 'a {
+    let mut seen = HashSet::new();
+    list.iter()
+        .map(|(other, _)| *other)
+        .filter(move |other| other != vertex && seen.insert(*other))
+}
+
+/// The entries of `list` pointing at `vertex`, with their positions.
+fn positions<K: Eq + Copy, T: Clone>(list: &LinkedList<(K, T)>, vertex: &K) -> Removed<K, T> {
+    list.iter()
+        .enumerate()
+        .filter(|(_, (other, _))| other == vertex)
+        .map(|(position, edge)| (position, edge.clone()))
+        .collect()
+}
+
+fn insert_at<E>(list: &mut LinkedList<E>, position: usize, element: E) {
+    let mut tail = list.split_off(position);
+    list.push_back(element);
+    list.append(&mut tail);
+}
+
+/// Tracks which vertices and edges would exist part way through a transaction,
+/// without touching the graph.
+struct Shadow<'a, K, T, V> {
+    graph: &'a Graph<K, T, V>,
+    vertices: HashMap<K, bool>,
+    /// Vertices removed at some point, whose original edges are gone for good.
+    cleared: HashSet<K>,
+    edges: HashMap<(K, K), usize>,
+}
+
+impl<'a, K, T, V> Shadow<'a, K, T, V>
+where
+    K: hash::Hash + Eq + Copy + Debug + Ord,
+    T: Debug + Clone,
+{
+    fn new(graph: &'a Graph<K, T, V>) -> Self {
+        Shadow {
+            graph,
+            vertices: HashMap::new(),
+            cleared: HashSet::new(),
+            edges: HashMap::new(),
+        }
+    }
+
+    fn has_vertex(&self, vertex: &K) -> bool {
+        self.vertices
+            .get(vertex)
+            .copied()
+            .unwrap_or_else(|| self.graph.vertex_exists(vertex))
+    }
+
+    fn edge_count(&mut self, from: K, to: K) -> &mut usize {
+        let (graph, cleared) = (self.graph, &self.cleared);
+        self.edges.entry((from, to)).or_insert_with(|| {
+            if cleared.contains(&from) || cleared.contains(&to) {
+                return 0;
+            }
+            graph
+                .edges_from(&from)
+                .filter(|(neighbor, _)| **neighbor == to)
+                .count()
+        })
+    }
+
+    fn require_vertex(&self, index: usize, vertex: K) -> Result<(), TransactionError<K>> {
+        match self.has_vertex(&vertex) {
+            true => Ok(()),
+            false => Err(TransactionError::MissingVertex { index, vertex }),
+        }
+    }
+
+    fn require_edge(&mut self, index: usize, from: K, to: K) -> Result<(), TransactionError<K>> {
+        self.require_vertex(index, from)?;
+        self.require_vertex(index, to)?;
+        match *self.edge_count(from, to) {
+            0 => Err(TransactionError::MissingEdge { index, from, to }),
+            _ => Ok(()),
+        }
+    }
+
+    fn check(&mut self, index: usize, change: &Change<K, T, V>) -> Result<(), TransactionError<K>> {
+        match change {
+            Change::InsertVertex(vertex) | Change::InsertVertexWithData(vertex, _) => {
+                if self.has_vertex(vertex) {
+                    return Err(TransactionError::VertexExists {
+                        index,
+                        vertex: *vertex,
+                    });
+                }
+                self.vertices.insert(*vertex, true);
+            }
+            Change::SetVertexData(vertex, _) => self.require_vertex(index, *vertex)?,
+            Change::RemoveVertex(vertex) => {
+                self.require_vertex(index, *vertex)?;
+                self.vertices.insert(*vertex, false);
+                self.cleared.insert(*vertex);
+                self.edges
+                    .iter_mut()
+                    .filter(|((from, to), _)| from == vertex || to == vertex)
+                    .for_each(|(_, count)| *count = 0);
+            }
+            Change::InsertEdge(from, to, _) => {
+                self.require_vertex(index, *from)?;
+                self.require_vertex(index, *to)?;
+                *self.edge_count(*from, *to) += 1;
+            }
+            Change::SetEdgeData(from, to, _) => self.require_edge(index, *from, *to)?,
+            Change::RemoveEdge(from, to) => {
+                self.require_edge(index, *from, *to)?;
+                *self.edge_count(*from, *to) -= 1;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The most recent applied transactions, newest last, bounded by `limit`.
+#[derive(Debug)]
+pub struct UndoLog<K, T, V = ()> {
+    batches: VecDeque<Undo<K, T, V>>,
+    limit: usize,
+}
+
+impl<K, T, V> UndoLog<K, T, V>
+where
+    K: hash::Hash + Eq + Copy + Debug + Ord,
+    T: Debug + Clone,
+{
+    /// Keeps at most `limit` batches; older ones are forgotten and can no longer be reverted.
+    pub fn new(limit: usize) -> UndoLog<K, T, V> {
+        UndoLog {
+            batches: VecDeque::new(),
+            limit,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.batches.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.batches.is_empty()
+    }
+
+    /// Applies `transaction` to `graph` and records it if it succeeded.
+    pub fn apply(
+        &mut self,
+        graph: &mut Graph<K, T, V>,
+        transaction: Transaction<K, T, V>,
+    ) -> Result<(), TransactionError<K>> {
+        let undo = graph.apply(transaction)?;
+        if self.limit == 0 {
+            return Ok(());
+        }
+        if self.batches.len() == self.limit {
+            self.batches.pop_front();
+        }
+        self.batches.push_back(undo);
+        Ok(())
+    }
+
+    /// Reverts up to `count` of the most recent batches, newest first, returning how many
+    /// were reverted. Stops at the first batch `graph` has changed since, which stays in
+    /// the log.
+    pub fn undo(&mut self, graph: &mut Graph<K, T, V>, count: usize) -> usize {
+        (0..count)
+            .map_while(|_| {
+                let undo = self.batches.pop_back()?;
+                if !graph.can_undo(&undo) {
+                    self.batches.push_back(undo);
+                    return None;
+                }
+                graph.undo(undo).ok()
+            })
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Change, Transaction, TransactionError, UndoError, UndoLog};
+    use crate::{
+        graph::{
+            djikstra::{djikstra_with, DjikstraOptions, TieBreak},
+            Graph,
+        },
+        heap::Heap,
+    };
+
+    fn sample() -> Graph<i64, u64, &'static str> {
+        let mut graph = Graph::with_vertex_data();
+        graph.insert_vertex_with_data(0, "zero");
+        graph.insert_vertex(1);
+        graph.insert_vertex(2);
+
+        graph.insert_edge(0, 1, 10);
+        graph.insert_edge(1, 0, 10);
+        graph.insert_edge(1, 2, 20);
+        graph.insert_edge(2, 2, 5);
+        graph.insert_edge(2, 0, 30);
+        graph
+    }
+
+    #[test]
+    fn failure_leaves_graph_untouched() {
+        let mut graph = sample();
+        let mut transaction = Transaction::new();
+        transaction
+            .insert_vertex(3)
+            .insert_edge(3, 0, 1)
+            .remove_vertex(1)
+            .insert_edge(0, 1, 1);
+
+        assert_eq!(
+            graph.apply(transaction).err(),
+            Some(TransactionError::MissingVertex {
+                index: 3,
+                vertex: 1
+            })
+        );
+        assert_eq!(graph, sample());
+    }
+
+    #[test]
+    fn validation_follows_earlier_changes() {
+        let graph = sample();
+
+        let transaction =
+            Transaction::from_iter([Change::RemoveEdge(0, 1), Change::RemoveEdge(0, 1)]);
+        assert_eq!(
+            graph.validate(&transaction),
+            Err(TransactionError::MissingEdge {
+                index: 1,
+                from: 0,
+                to: 1
+            })
+        );
+
+        let transaction = Transaction::from_iter([
+            Change::RemoveVertex(2),
+            Change::InsertVertex(2),
+            Change::SetEdgeData(1, 2, 7),
+        ]);
+        assert_eq!(
+            graph.validate(&transaction),
+            Err(TransactionError::MissingEdge {
+                index: 2,
+                from: 1,
+                to: 2
+            })
+        );
+
+        let transaction = Transaction::from_iter([Change::InsertVertex(0)]);
+        assert_eq!(
+            graph.validate(&transaction),
+            Err(TransactionError::VertexExists {
+                index: 0,
+                vertex: 0
+            })
+        );
+    }
+
+    #[test]
+    fn apply_and_undo() {
+        let mut graph = sample();
+        let mut transaction = Transaction::new();
+        transaction
+            .insert_vertex_with_data(3, "three")
+            .insert_edge(3, 2, 1)
+            .set_edge_data(1, 0, 15)
+            .set_vertex_data(0, "nil")
+            .remove_edge(0, 1)
+            .remove_vertex(2);
+
+        let undo = graph.apply(transaction).unwrap();
+        assert_eq!(graph.vertex_count(), 3);
+        assert_eq!(graph.vertex_data(&0), Some(&"nil"));
+        assert_eq!(graph.edge_data(&1, &0), Some(&15));
+        assert!(!graph.edge_exists(&0, &1));
+        assert!(!graph.edge_exists(&3, &2));
+
+        assert_eq!(graph.undo(undo), Ok(()));
+        assert_eq!(graph, sample());
+        assert_eq!(graph.in_edges(&2).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn undo_keeps_adjacency_order() {
+        let mut graph = Graph::<i64, u64>::new();
+        (0..5).for_each(|vertex| {
+            graph.insert_vertex(vertex);
+        });
+        [
+            (0, 1, 1),
+            (0, 2, 2),
+            (0, 3, 3),
+            (0, 2, 4),
+            (0, 4, 5),
+            (1, 2, 6),
+            (1, 0, 7),
+            (2, 3, 8),
+            (2, 2, 9),
+            (2, 1, 10),
+            (3, 2, 11),
+            (4, 3, 12),
+        ]
+        .into_iter()
+        .for_each(|(from, to, value)| {
+            graph.insert_edge(from, to, value);
+        });
+        let original = graph.clone();
+        let order = |graph: &Graph<i64, u64>| {
+            let options = DjikstraOptions {
+                tie_break: TieBreak::InsertionOrder,
+                ..DjikstraOptions::default()
+            };
+            Vec::from_iter(
+                djikstra_with(graph, 0, Heap::new(), options)
+                    .into_iter()
+                    .map(|node| (node.vertex, node.prev)),
+            )
+        };
+
+        let mut transaction = Transaction::new();
+        transaction
+            .remove_edge(0, 3)
+            .remove_edge(0, 2)
+            .remove_vertex(2)
+            .remove_edge(4, 3)
+            .insert_vertex(2)
+            .insert_edge(2, 0, 13);
+        let undo = graph.apply(transaction).unwrap();
+        assert_eq!(graph.undo(undo), Ok(()));
+
+        assert_eq!(graph.content, original.content);
+        assert_eq!(graph.incoming, original.incoming);
+        assert_eq!(order(&graph), order(&original));
+    }
+
+    #[test]
+    fn undo_log() {
+        let mut graph = sample();
+        let mut log = UndoLog::new(2);
+
+        (3..6).for_each(|vertex| {
+            let mut transaction = Transaction::new();
+            transaction
+                .insert_vertex(vertex)
+                .insert_edge(vertex, vertex - 1, 1);
+            log.apply(&mut graph, transaction).unwrap();
+        });
+        assert_eq!(log.len(), 2);
+        assert_eq!(graph.vertex_count(), 6);
+
+        let mut failing = Transaction::new();
+        failing.remove_edge(5, 0);
+        assert!(log.apply(&mut graph, failing).is_err());
+        assert_eq!(log.len(), 2);
+
+        assert_eq!(log.undo(&mut graph, 5), 2);
+        assert!(log.is_empty());
+
+        let mut expected = sample();
+        expected.insert_vertex(3);
+        expected.insert_edge(3, 2, 1);
+        assert_eq!(graph, expected);
+    }
+
+    #[test]
+    fn undo_after_outside_change() {
+        let mut graph = sample();
+        let mut transaction = Transaction::new();
+        transaction.remove_edge(1, 2).remove_vertex(0);
+        let undo = graph.apply(transaction).unwrap();
+
+        graph.remove_edge(&2, &2);
+        let changed = graph.clone();
+        assert!(!graph.can_undo(&undo));
+        assert_eq!(graph.undo(undo), Err(UndoError::GraphChanged));
+        assert_eq!(graph.content, changed.content);
+        assert_eq!(graph.incoming, changed.incoming);
+
+        let mut log = UndoLog::new(3);
+        (3..5).for_each(|vertex| {
+            let mut transaction = Transaction::new();
+            transaction.insert_vertex(vertex).insert_edge(vertex, 1, 1);
+            log.apply(&mut graph, transaction).unwrap();
+        });
+        assert_eq!(log.undo(&mut graph, 1), 1);
+        graph.insert_edge(3, 2, 1);
+        assert_eq!(log.undo(&mut graph, 1), 0);
+        assert_eq!(log.len(), 1);
+        assert!(graph.vertex_exists(&3));
+    }
+}
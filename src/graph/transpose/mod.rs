@@ -0,0 +1,173 @@
+use std::{
+    collections::{hash_map, HashSet, LinkedList},
+    fmt::Debug,
+    hash, iter, option,
+};
+
+use super::{
+    traits::{EdgeWeight, GraphBase, Neighbors, VertexSet},
+    Graph,
+};
+
+/// Borrowed view of a [`Graph`] with every edge reversed, backed by its incoming-edge index.
+#[derive(Debug)]
+pub struct Transposed<'a, K, T, V = ()> {
+    graph: &'a Graph<K, T, V>,
+}
+
+impl<'a, K, T, V> Transposed<'a, K, T, V>
+where
+    K: hash::Hash + Eq + Copy + Debug + Ord,
+    T: Debug + Clone,
+{
+    pub fn new(graph: &'a Graph<K, T, V>) -> Transposed<'a, K, T, V> {
+        Transposed { graph }
+    }
+
+    /// The graph this view reverses.
+    pub fn transpose(&self) -> &'a Graph<K, T, V> {
+        self.graph
+    }
+
+    pub fn vertex_exists(&self, vertex: &K) -> bool {
+        self.graph.vertex_exists(vertex)
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        self.graph.vertex_count()
+    }
+
+    pub fn vertex_data(&self, vertex: &K) -> Option<&'a V> {
+        self.graph.vertex_data(vertex)
+    }
+
+    pub fn adjacency_list(&self, vertex: &K) -> Option<&'a LinkedList<(K, T)>> {
+        self.graph.in_edges(vertex)
+    }
+
+    pub fn in_edges(&self, vertex: &K) -> Option<&'a LinkedList<(K, T)>> {
+        self.graph.adjacency_list(vertex)
+    }
+
+    pub fn edge_exists(&self, from: &K, to: &K) -> bool {
+        self.graph.edge_exists(to, from)
+    }
+
+    pub fn out_neighbors(&self, vertex: &K) -> Option<HashSet<&'a K>> {
+        Some(
+            self.adjacency_list(vertex)?
+                .iter()
+                .map(|(out_neighbor, _)| out_neighbor)
+                .collect(),
+        )
+    }
+
+    pub fn in_neighbors(&self, vertex: &K) -> Option<HashSet<&'a K>> {
+        Some(
+            self.in_edges(vertex)?
+                .iter()
+                .map(|(in_neighbor, _)| in_neighbor)
+                .filter(|in_neighbor| *in_neighbor != vertex)
+                .collect(),
+        )
+    }
+}
+
+impl<K, T, V> GraphBase for Transposed<'_, K, T, V>
+where
+    K: hash::Hash + Eq + Copy,
+{
+    type Key = K;
+    type Weight = T;
+}
+
+impl<K, T, V> VertexSet for Transposed<'_, K, T, V>
+where
+    K: hash::Hash + Eq + Copy,
+{
+    type VertexKeys<'b>
+        = iter::Copied<hash_map::Keys<'b, K, LinkedList<(K, T)>>>
+    where
+        Self: 'b;
+
+    fn vertex_keys(&self) -> Self::VertexKeys<'_> {
+        self.graph.vertex_keys()
+    }
+
+    fn has_vertex(&self, vertex: &K) -> bool {
+        self.graph.has_vertex(vertex)
+    }
+}
+
+impl<K, T, V> Neighbors for Transposed<'_, K, T, V>
+where
+    K: hash::Hash + Eq + Copy,
+    T: Clone,
+{
+    type Successors<'b>
+        = iter::Cloned<iter::Flatten<option::IntoIter<&'b LinkedList<(K, T)>>>>
+    where
+        Self: 'b;
+
+    fn successors(&self, vertex: &K) -> Self::Successors<'_> {
+        self.graph
+            .incoming
+            .get(vertex)
+            .into_iter()
+            .flatten()
+            .cloned()
+    }
+}
+
+impl<K, T, V> EdgeWeight for Transposed<'_, K, T, V>
+where
+    K: hash::Hash + Eq + Copy,
+    T: Clone,
+{
+    fn edge_weight(&self, from: &K, to: &K) -> Option<T> {
+        self.graph.edge_weight(to, from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::graph::{djikstra::djikstra, traits::EdgeWeight, Graph};
+
+    #[test]
+    fn reversed_edges() {
+        let mut graph = Graph::<i64, u64>::new();
+        graph.insert_vertex(0);
+        graph.insert_vertex(1);
+        graph.insert_vertex(2);
+        graph.insert_edge(0, 1, 5);
+        graph.insert_edge(1, 2, 7);
+
+        let transposed = graph.transpose();
+        assert!(transposed.edge_exists(&1, &0));
+        assert!(!transposed.edge_exists(&0, &1));
+        assert_eq!(transposed.out_neighbors(&2), Some(HashSet::from([&1])));
+        assert_eq!(transposed.in_neighbors(&0), Some(HashSet::from([&1])));
+        assert!(transposed.transpose().edge_exists(&0, &1));
+        assert_eq!(transposed.edge_weight(&2, &1), Some(7));
+        assert_eq!(transposed.edge_weight(&1, &2), None);
+    }
+
+    #[test]
+    fn reverse_search() {
+        let mut graph = Graph::<i64, u64>::new();
+        graph.insert_vertex(0);
+        graph.insert_vertex(1);
+        graph.insert_vertex(2);
+        graph.insert_edge(0, 1, 5);
+        graph.insert_edge(1, 2, 7);
+
+        let results = djikstra(&graph.transpose(), 2);
+        let distances: Vec<_> = results
+            .iter()
+            .map(|node| (node.vertex, node.distance))
+            .collect();
+        assert_eq!(distances, vec![(2, 0), (1, 7), (0, 12)]);
+    }
+}
@@ -0,0 +1,126 @@
+use super::queue::{Handle, Slots};
+
+/// Lists of elements with handles, shared by the integer queues. Every list is kept as a
+/// binary min-heap, so the smallest element of a bucket is always first and elements
+/// that share a key still come out in `Ord` order at `O(log n)` per operation.
+/// A handle's location is its `(bucket, position)` pair.
+pub(crate) struct Buckets<T> {
+    lists: Vec<Vec<(T, u32)>>,
+    slots: Slots<(usize, usize)>,
+    len: usize,
+}
+
+impl<T> Buckets<T> {
+    pub(crate) fn new(count: usize) -> Self {
+        Buckets {
+            lists: Vec::from_iter((0..count).map(|_| Vec::new())),
+            slots: Slots::default(),
+            len: 0,
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    pub(crate) fn list(&self, bucket: usize) -> &[(T, u32)] {
+        &self.lists[bucket]
+    }
+
+    pub(crate) fn locate(&self, handle: Handle) -> Option<(usize, usize)> {
+        self.slots.get(handle)
+    }
+
+    pub(crate) fn get(&self, handle: Handle) -> Option<&T> {
+        let (bucket, position) = self.locate(handle)?;
+        Some(&self.lists[bucket][position].0)
+    }
+
+    /// Smallest element of `bucket`.
+    pub(crate) fn first(&self, bucket: usize) -> Option<&T> {
+        self.lists[bucket].first().map(|(element, _)| element)
+    }
+
+    /// Empties `bucket`, keeping the handles of its elements reserved.
+    pub(crate) fn drain(&mut self, bucket: usize) -> Vec<(T, u32)> {
+        self.len -= self.lists[bucket].len();
+        std::mem::take(&mut self.lists[bucket])
+    }
+}
+
+impl<T: Ord> Buckets<T> {
+    pub(crate) fn insert(&mut self, bucket: usize, element: T) -> Handle {
+        let handle = self.slots.insert((bucket, self.lists[bucket].len()));
+        self.lists[bucket].push((element, handle.slot()));
+        self.len += 1;
+        self.sift_up(bucket, self.lists[bucket].len() - 1);
+        handle
+    }
+
+    /// Puts an element taken out with [`Buckets::take`] back under its old handle.
+    pub(crate) fn reinsert(&mut self, bucket: usize, element: T, slot: u32) {
+        self.slots
+            .relocate(slot, (bucket, self.lists[bucket].len()));
+        self.lists[bucket].push((element, slot));
+        self.len += 1;
+        self.sift_up(bucket, self.lists[bucket].len() - 1);
+    }
+
+    /// Takes an element out while keeping its handle reserved for [`Buckets::reinsert`].
+    pub(crate) fn take(&mut self, bucket: usize, position: usize) -> (T, u32) {
+        let taken = self.lists[bucket].swap_remove(position);
+        if let Some((_, moved)) = self.lists[bucket].get(position) {
+            self.slots.relocate(*moved, (bucket, position));
+            let position = self.sift_up(bucket, position);
+            self.sift_down(bucket, position);
+        }
+        self.len -= 1;
+        taken
+    }
+
+    /// Takes the smallest element of `bucket` out for good.
+    pub(crate) fn remove_first(&mut self, bucket: usize) -> Option<T> {
+        if self.lists[bucket].is_empty() {
+            return None;
+        }
+        let (element, slot) = self.take(bucket, 0);
+        self.slots.remove(slot);
+        Some(element)
+    }
+
+    /// Moves the element at `position` up to its place and returns where it ended up.
+    fn sift_up(&mut self, bucket: usize, mut position: usize) -> usize {
+        while position > 0 {
+            let parent = (position - 1) / 2;
+            if self.lists[bucket][parent].0 <= self.lists[bucket][position].0 {
+                break;
+            }
+            self.swap(bucket, parent, position);
+            position = parent;
+        }
+        position
+    }
+
+    fn sift_down(&mut self, bucket: usize, mut position: usize) {
+        loop {
+            let list = &self.lists[bucket];
+            let mut smallest = position;
+            for child in [2 * position + 1, 2 * position + 2] {
+                if child < list.len() && list[child].0 < list[smallest].0 {
+                    smallest = child;
+                }
+            }
+            if smallest == position {
+                return;
+            }
+            self.swap(bucket, position, smallest);
+            position = smallest;
+        }
+    }
+
+    fn swap(&mut self, bucket: usize, a: usize, b: usize) {
+        self.lists[bucket].swap(a, b);
+        self.slots.relocate(self.lists[bucket][a].1, (bucket, a));
+        self.slots.relocate(self.lists[bucket][b].1, (bucket, b));
+    }
+}
@@ -0,0 +1,44 @@
+use std::cmp::Ordering;
+
+/// Decides the order of a [`Heap`](super::Heap): elements comparing as `Less` come out first.
+///
+/// Any `Fn(&T, &T) -> Ordering` closure is a comparator as well.
+pub trait Compare<T> {
+    fn compare(&self, a: &T, b: &T) -> Ordering;
+}
+
+/// Smallest element first, by `Ord`.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct MinOrder;
+
+/// Largest element first, by `Ord`.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct MaxOrder;
+
+/// Smallest key first, where the key is projected out of each element.
+#[derive(Copy, Clone, Debug)]
+pub struct ByKey<F>(pub F);
+
+impl<T: Ord> Compare<T> for MinOrder {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+impl<T: Ord> Compare<T> for MaxOrder {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        b.cmp(a)
+    }
+}
+
+impl<T, K: Ord, F: Fn(&T) -> K> Compare<T> for ByKey<F> {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        (self.0)(a).cmp(&(self.0)(b))
+    }
+}
+
+impl<T, F: Fn(&T, &T) -> Ordering> Compare<T> for F {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        self(a, b)
+    }
+}
@@ -0,0 +1,117 @@
+use super::queue::{Handle, HeapError, PriorityQueue, Slots};
+
+/// Min-heap in which every node has `D` children. A wider tree is shallower, which makes
+/// `decrease_key` cheaper at the cost of more comparisons per level in `pop`.
+pub struct DaryHeap<T, const D: usize> {
+    data: Vec<T>,
+    /// Handle slot of the element at each position of `data`.
+    owners: Vec<u32>,
+    slots: Slots,
+}
+
+impl<T: Ord, const D: usize> DaryHeap<T, D> {
+    pub fn new() -> Self {
+        const { assert!(D >= 2, "a d-ary heap needs at least two children per node") };
+
+        DaryHeap {
+            data: Vec::new(),
+            owners: Vec::new(),
+            slots: Slots::default(),
+        }
+    }
+
+    fn parent(idx: usize) -> usize {
+        (idx - 1) / D
+    }
+
+    fn sift_up(&mut self, idx: usize) {
+        let mut current = idx;
+
+        while current > 0 && self.data[current] < self.data[Self::parent(current)] {
+            let parent = Self::parent(current);
+            self.swap(current, parent);
+            current = parent;
+        }
+    }
+
+    fn sift_down(&mut self, idx: usize) {
+        let mut current = idx;
+
+        loop {
+            let first_child = current * D + 1;
+            let smallest = (first_child..(first_child + D).min(self.data.len()))
+                .min_by(|a, b| self.data[*a].cmp(&self.data[*b]));
+
+            match smallest {
+                Some(child) if self.data[child] < self.data[current] => {
+                    self.swap(current, child);
+                    current = child;
+                }
+                _ => break,
+            }
+        }
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        self.data.swap(a, b);
+        self.owners.swap(a, b);
+        self.slots.relocate(self.owners[a], a);
+        self.slots.relocate(self.owners[b], b);
+    }
+}
+
+impl<T: Ord, const D: usize> PriorityQueue<T> for DaryHeap<T, D> {
+    type Handle = Handle;
+
+    fn push(&mut self, element: T) -> Handle {
+        let handle = self.slots.insert(self.data.len());
+        self.data.push(element);
+        self.owners.push(handle.slot());
+        self.sift_up(self.data.len() - 1);
+        handle
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        if self.data.is_empty() {
+            return None;
+        }
+
+        let last = self.data.len() - 1;
+        self.swap(0, last);
+        self.slots.remove(self.owners.pop().unwrap());
+        let value = self.data.pop();
+        if !self.data.is_empty() {
+            self.sift_down(0);
+        }
+        value
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    fn get(&self, handle: Handle) -> Option<&T> {
+        self.slots.get(handle).map(|idx| &self.data[idx])
+    }
+
+    fn decrease_key(&mut self, handle: Handle, element: T) -> Result<(), HeapError> {
+        let idx = self.slots.get(handle).ok_or(HeapError::InvalidHandle)?;
+        if element > self.data[idx] {
+            return Err(HeapError::KeyIncreased);
+        }
+
+        self.data[idx] = element;
+        self.sift_up(idx);
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+}
+
+impl<T: Ord, const D: usize> Default for DaryHeap<T, D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
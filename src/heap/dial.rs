@@ -0,0 +1,122 @@
+use super::{
+    bucket::Buckets,
+    queue::{Handle, HeapError, PriorityQueue},
+    radix::RadixKey,
+};
+
+/// Dial's bucket queue: a monotone min-queue for integer keys that never lie more than
+/// `max_span` above the last popped key. In Dijkstra's algorithm `max_span` is the
+/// largest edge weight.
+///
+/// Keys map to a circular array of `max_span + 1` buckets, so all elements in a bucket
+/// share a key and popping only has to walk forward to the next non-empty bucket. Each
+/// bucket is a small binary heap, so many elements with the same key still pop in
+/// `O(log n)` each.
+pub struct DialQueue<T> {
+    buckets: Buckets<T>,
+    last: u64,
+    max_span: u64,
+    /// Number of buckets, `max_span + 1`.
+    width: u64,
+}
+
+impl<T: Ord + RadixKey> DialQueue<T> {
+    /// # Panics
+    ///
+    /// If `max_span + 1` buckets cannot be addressed on this platform.
+    pub fn new(max_span: u64) -> Self {
+        let width = max_span
+            .checked_add(1)
+            .filter(|width| usize::try_from(*width).is_ok())
+            .unwrap_or_else(|| panic!("span {max_span} needs more buckets than fit in memory"));
+
+        DialQueue {
+            buckets: Buckets::new(width as usize),
+            last: 0,
+            max_span,
+            width,
+        }
+    }
+
+    /// Like [`PriorityQueue::push`], but returns [`HeapError::NotMonotone`] instead of
+    /// panicking when the key is outside the window.
+    pub fn try_push(&mut self, element: T) -> Result<Handle, HeapError> {
+        match self.in_window(element.radix_key()) {
+            true => Ok(self
+                .buckets
+                .insert(self.bucket(element.radix_key()), element)),
+            false => Err(HeapError::NotMonotone),
+        }
+    }
+
+    fn bucket(&self, key: u64) -> usize {
+        (key % self.width) as usize
+    }
+
+    fn in_window(&self, key: u64) -> bool {
+        key >= self.last && key - self.last <= self.max_span
+    }
+
+    /// The next key at or after the last popped one with a non-empty bucket.
+    fn next_key(&self) -> Option<u64> {
+        (self.buckets.len() > 0).then(|| {
+            (self.last..)
+                .find(|key| !self.buckets.list(self.bucket(*key)).is_empty())
+                .unwrap()
+        })
+    }
+}
+
+impl<T: Ord + RadixKey> PriorityQueue<T> for DialQueue<T> {
+    type Handle = Handle;
+
+    /// # Panics
+    ///
+    /// If the key of `element` is below the last popped key or more than `max_span`
+    /// above it; [`DialQueue::try_push`] reports that instead.
+    fn push(&mut self, element: T) -> Handle {
+        let key = element.radix_key();
+        assert!(
+            self.in_window(key),
+            "key {key} outside {}..={}",
+            self.last,
+            self.last.saturating_add(self.max_span)
+        );
+        self.buckets.insert(self.bucket(key), element)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.last = self.next_key()?;
+        self.buckets.remove_first(self.bucket(self.last))
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.buckets.first(self.bucket(self.next_key()?))
+    }
+
+    fn get(&self, handle: Handle) -> Option<&T> {
+        self.buckets.get(handle)
+    }
+
+    fn decrease_key(&mut self, handle: Handle, element: T) -> Result<(), HeapError> {
+        let (bucket, position) = self
+            .buckets
+            .locate(handle)
+            .ok_or(HeapError::InvalidHandle)?;
+        if element > self.buckets.list(bucket)[position].0 {
+            return Err(HeapError::KeyIncreased);
+        }
+        if !self.in_window(element.radix_key()) {
+            return Err(HeapError::NotMonotone);
+        }
+
+        let (_, slot) = self.buckets.take(bucket, position);
+        let target = self.bucket(element.radix_key());
+        self.buckets.reinsert(target, element, slot);
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.buckets.len()
+    }
+}
@@ -0,0 +1,252 @@
+use super::queue::{Handle, HeapError, PriorityQueue, Slots};
+
+/// Min-heap made of a list of heap-ordered trees. Pushing and lowering a key are
+/// amortized `O(1)`; `pop` consolidates trees of equal degree, amortized `O(log n)`.
+pub struct FibonacciHeap<T> {
+    nodes: Vec<Option<FibonacciNode<T>>>,
+    free: Vec<usize>,
+    min: Option<usize>,
+    len: usize,
+    slots: Slots,
+}
+
+struct FibonacciNode<T> {
+    element: T,
+    parent: Option<usize>,
+    child: Option<usize>,
+    /// Neighbors in the circular list of roots or siblings.
+    left: usize,
+    right: usize,
+    degree: usize,
+    /// Whether the node lost a child since it last became a child itself.
+    marked: bool,
+    slot: u32,
+}
+
+impl<T: Ord> FibonacciHeap<T> {
+    pub fn new() -> Self {
+        FibonacciHeap {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            min: None,
+            len: 0,
+            slots: Slots::default(),
+        }
+    }
+
+    fn node(&self, idx: usize) -> &FibonacciNode<T> {
+        self.nodes[idx].as_ref().unwrap()
+    }
+
+    fn node_mut(&mut self, idx: usize) -> &mut FibonacciNode<T> {
+        self.nodes[idx].as_mut().unwrap()
+    }
+
+    /// Links the lone node `idx` into a circular list right after `after`.
+    fn insert_after(&mut self, after: usize, idx: usize) {
+        let right = self.node(after).right;
+        let node = self.node_mut(idx);
+        node.left = after;
+        node.right = right;
+        self.node_mut(right).left = idx;
+        self.node_mut(after).right = idx;
+    }
+
+    /// Unlinks `idx` from its circular list, leaving it alone in a list of its own.
+    fn unlink(&mut self, idx: usize) {
+        let FibonacciNode { left, right, .. } = *self.node(idx);
+        self.node_mut(left).right = right;
+        self.node_mut(right).left = left;
+        let node = self.node_mut(idx);
+        node.left = idx;
+        node.right = idx;
+    }
+
+    fn list(&self, first: usize) -> Vec<usize> {
+        let mut list = vec![first];
+        let mut current = self.node(first).right;
+        while current != first {
+            list.push(current);
+            current = self.node(current).right;
+        }
+        list
+    }
+
+    fn add_root(&mut self, idx: usize) {
+        let node = self.node_mut(idx);
+        node.parent = None;
+        node.marked = false;
+
+        match self.min {
+            Some(min) => {
+                self.insert_after(min, idx);
+                if self.node(idx).element < self.node(min).element {
+                    self.min = Some(idx);
+                }
+            }
+            None => self.min = Some(idx),
+        }
+    }
+
+    /// Makes the root `child` a child of the root `parent`.
+    fn link(&mut self, child: usize, parent: usize) {
+        self.unlink(child);
+        match self.node(parent).child {
+            Some(first) => self.insert_after(first, child),
+            None => self.node_mut(parent).child = Some(child),
+        }
+
+        let node = self.node_mut(child);
+        node.parent = Some(parent);
+        node.marked = false;
+        self.node_mut(parent).degree += 1;
+    }
+
+    /// Merges roots until no two have the same degree, then finds the new minimum.
+    fn consolidate(&mut self, first: usize) {
+        let mut by_degree: Vec<Option<usize>> = Vec::new();
+
+        for root in self.list(first) {
+            let mut current = root;
+            let mut degree = self.node(current).degree;
+
+            while let Some(other) = by_degree.get_mut(degree).and_then(Option::take) {
+                let (parent, child) = match self.node(other).element < self.node(current).element {
+                    true => (other, current),
+                    false => (current, other),
+                };
+                self.link(child, parent);
+                current = parent;
+                degree += 1;
+            }
+
+            if by_degree.len() <= degree {
+                by_degree.resize(degree + 1, None);
+            }
+            by_degree[degree] = Some(current);
+        }
+
+        self.min = None;
+        by_degree.into_iter().flatten().for_each(|root| {
+            self.unlink(root);
+            self.add_root(root);
+        });
+    }
+
+    /// Moves `idx` from the children of `parent` to the roots.
+    fn cut(&mut self, idx: usize, parent: usize) {
+        if self.node(parent).child == Some(idx) {
+            let right = self.node(idx).right;
+            self.node_mut(parent).child = (right != idx).then_some(right);
+        }
+        self.unlink(idx);
+        self.node_mut(parent).degree -= 1;
+        self.add_root(idx);
+    }
+
+    fn cascading_cut(&mut self, idx: usize) {
+        let mut current = idx;
+
+        while let Some(parent) = self.node(current).parent {
+            if !self.node(current).marked {
+                self.node_mut(current).marked = true;
+                break;
+            }
+
+            self.cut(current, parent);
+            current = parent;
+        }
+    }
+}
+
+impl<T: Ord> PriorityQueue<T> for FibonacciHeap<T> {
+    type Handle = Handle;
+
+    fn push(&mut self, element: T) -> Handle {
+        let idx = self.free.pop().unwrap_or(self.nodes.len());
+        let handle = self.slots.insert(idx);
+        let node = FibonacciNode {
+            element,
+            parent: None,
+            child: None,
+            left: idx,
+            right: idx,
+            degree: 0,
+            marked: false,
+            slot: handle.slot(),
+        };
+        match idx == self.nodes.len() {
+            true => self.nodes.push(Some(node)),
+            false => self.nodes[idx] = Some(node),
+        }
+
+        self.add_root(idx);
+        self.len += 1;
+        handle
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        let min = self.min?;
+
+        if let Some(child) = self.node(min).child {
+            for idx in self.list(child) {
+                self.unlink(idx);
+                self.insert_after(min, idx);
+                self.node_mut(idx).parent = None;
+            }
+        }
+
+        let next = self.node(min).right;
+        self.unlink(min);
+        let node = self.nodes[min].take().unwrap();
+        self.free.push(min);
+        self.slots.remove(node.slot);
+        self.len -= 1;
+
+        self.min = None;
+        if next != min {
+            self.consolidate(next);
+        }
+        Some(node.element)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.min.map(|min| &self.node(min).element)
+    }
+
+    fn get(&self, handle: Handle) -> Option<&T> {
+        self.slots.get(handle).map(|idx| &self.node(idx).element)
+    }
+
+    fn decrease_key(&mut self, handle: Handle, element: T) -> Result<(), HeapError> {
+        let idx = self.slots.get(handle).ok_or(HeapError::InvalidHandle)?;
+        if element > self.node(idx).element {
+            return Err(HeapError::KeyIncreased);
+        }
+
+        self.node_mut(idx).element = element;
+        match self.node(idx).parent {
+            Some(parent) if self.node(idx).element < self.node(parent).element => {
+                self.cut(idx, parent);
+                self.cascading_cut(parent);
+            }
+            _ => {
+                let min = self.min.unwrap();
+                if self.node(idx).element < self.node(min).element {
+                    self.min = Some(idx);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<T: Ord> Default for FibonacciHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
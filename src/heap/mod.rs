@@ -1,50 +1,244 @@
-pub struct Heap<T: Ord + Copy> {
-    pub data: Vec<T>,
+mod bucket;
+pub mod compare;
+pub mod dary;
+pub mod dial;
+pub mod fibonacci;
+pub mod pairing;
+pub mod queue;
+pub mod radix;
+
+use std::cmp::Ordering;
+
+use self::compare::{ByKey, Compare, MaxOrder, MinOrder};
+use self::queue::{Handle, HeapError, PriorityQueue, Slots};
+
+/// Binary heap ordered by the comparator `C`, smallest first by default. Elements that
+/// compare equal come out in the order they were inserted.
+pub struct Heap<T, C = MinOrder> {
+    data: Vec<T>,
+    /// Handle slot of the element at each position of `data`.
+    owners: Vec<u32>,
+    /// Insertion number of the element at each position of `data`, for breaking ties.
+    stamps: Vec<u64>,
+    next_stamp: u64,
+    slots: Slots,
+    compare: C,
 }
 
-impl<T: Ord + Copy> Heap<T> {
+/// Binary heap that pops the largest element first.
+pub type MaxHeap<T> = Heap<T, MaxOrder>;
+
+impl<T: Ord> Heap<T> {
     pub fn new() -> Self {
-        Heap { data: vec![] }
+        Heap::with_comparator(MinOrder)
     }
 
-    pub fn insert(&mut self, element: T) {
+    /// Builds a heap out of `data` in `O(n)`.
+    pub fn from_vec(data: Vec<T>) -> Self {
+        Heap::from_vec_with_comparator(data, MinOrder)
+    }
+}
+
+impl<T: Ord> Heap<T, MaxOrder> {
+    pub fn new_max() -> Self {
+        Heap::with_comparator(MaxOrder)
+    }
+}
+
+impl<T, K: Ord, F: Fn(&T) -> K> Heap<T, ByKey<F>> {
+    /// Heap that pops the element with the smallest `key(element)` first.
+    pub fn by_key(key: F) -> Self {
+        Heap::with_comparator(ByKey(key))
+    }
+}
+
+impl<T, C: Compare<T>> Heap<T, C> {
+    pub fn with_comparator(compare: C) -> Self {
+        Heap {
+            data: vec![],
+            owners: vec![],
+            stamps: vec![],
+            next_stamp: 0,
+            slots: Slots::default(),
+            compare,
+        }
+    }
+
+    pub fn from_vec_with_comparator(data: Vec<T>, compare: C) -> Self {
+        let mut heap = Heap::with_comparator(compare);
+        heap.append_unordered(data);
+        heap
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Makes room for at least `additional` more elements without reallocating.
+    pub fn reserve(&mut self, additional: usize) {
+        self.data.reserve(additional);
+        self.owners.reserve(additional);
+        self.stamps.reserve(additional);
+        self.slots.reserve(additional);
+    }
+
+    /// Moves every element of `other` into this heap, as if inserted after all current
+    /// ones in their original order. Handles from `other` become invalid.
+    pub fn merge(&mut self, other: Heap<T, C>) {
+        let mut elements = Vec::from_iter(other.stamps.into_iter().zip(other.data));
+        elements.sort_by_key(|(stamp, _)| *stamp);
+        self.append_unordered(elements.into_iter().map(|(_, element)| element).collect());
+    }
+
+    /// Every element in the order [`Heap::pop`] would return them: ascending for a min-heap,
+    /// descending for a [`MaxHeap`], and by the comparator in general. This differs from
+    /// [`BinaryHeap::into_sorted_vec`](std::collections::BinaryHeap::into_sorted_vec), which
+    /// is always ascending.
+    pub fn into_ordered_vec(mut self) -> Vec<T> {
+        Vec::from_iter(self.pop_iter())
+    }
+
+    /// Pops elements one at a time as the iterator is advanced. Elements it did not get to
+    /// stay in the heap when it is dropped, unlike with
+    /// [`BinaryHeap::drain`](std::collections::BinaryHeap::drain).
+    pub fn pop_iter(&mut self) -> impl Iterator<Item = T> + '_ {
+        std::iter::from_fn(move || self.pop())
+    }
+
+    /// Adds `elements` without restoring the heap order, then rebuilds it bottom-up.
+    fn append_unordered(&mut self, elements: Vec<T>) {
+        elements.into_iter().for_each(|element| {
+            self.push_last(element);
+        });
+
+        (0..self.data.len() / 2)
+            .rev()
+            .for_each(|idx| self.heapify(idx));
+    }
+
+    /// Whether the element at `a` comes out before the one at `b`.
+    fn before(&self, a: usize, b: usize) -> bool {
+        self.compare
+            .compare(&self.data[a], &self.data[b])
+            .then(self.stamps[a].cmp(&self.stamps[b]))
+            == Ordering::Less
+    }
+
+    fn push_last(&mut self, element: T) -> Handle {
+        let handle = self.slots.insert(self.data.len());
         self.data.push(element);
-        let mut current = self.data.len() - 1;
+        self.owners.push(handle.slot());
+        self.stamps.push(self.next_stamp);
+        self.next_stamp += 1;
+        handle
+    }
+
+    fn pop_last(&mut self) -> Option<T> {
+        self.slots.remove(self.owners.pop()?);
+        self.stamps.pop();
+        self.data.pop()
+    }
+
+    /// Adds `element` and returns a handle for updating or removing it later.
+    pub fn insert(&mut self, element: T) -> Handle {
+        let handle = self.push_last(element);
+        self.sift_up(self.data.len() - 1);
+        handle
+    }
+
+    /// The element behind `handle`, or `None` once it has left the heap.
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        self.slots.get(handle).map(|idx| &self.data[idx])
+    }
+
+    fn locate(&self, handle: Handle) -> Result<usize, HeapError> {
+        self.slots.get(handle).ok_or(HeapError::InvalidHandle)
+    }
+
+    fn sift_up(&mut self, idx: usize) {
+        let mut current = idx;
 
         while current != 0 {
             let parent_idx = self.parent(current);
-
-            if self.data[current] < self.data[parent_idx] {
-                self.data.swap(current, parent_idx)
+            if !self.before(current, parent_idx) {
+                break;
             }
 
+            self.swap(current, parent_idx);
             current = parent_idx;
         }
     }
 
-    pub fn decrease_key(&mut self, idx: usize, element: T) {
-        if element > self.data[idx] {
-            panic!("New key is bigger than current key");
+    /// Replaces the element behind `handle` with one that comes out no later.
+    pub fn decrease_key(&mut self, handle: Handle, element: T) -> Result<(), HeapError> {
+        let idx = self.locate(handle)?;
+        if self.compare.compare(&element, &self.data[idx]) == Ordering::Greater {
+            return Err(HeapError::KeyIncreased);
         }
 
         self.data[idx] = element;
-        let mut current = idx;
+        self.sift_up(idx);
+        Ok(())
+    }
+
+    /// Replaces the element behind `handle` with one that comes out no earlier.
+    pub fn increase_key(&mut self, handle: Handle, element: T) -> Result<(), HeapError> {
+        let idx = self.locate(handle)?;
+        if self.compare.compare(&element, &self.data[idx]) == Ordering::Less {
+            return Err(HeapError::KeyDecreased);
+        }
+
+        self.data[idx] = element;
+        self.heapify(idx);
+        Ok(())
+    }
+
+    /// Replaces the element behind `handle` with any other, returning the old one.
+    pub fn update(&mut self, handle: Handle, element: T) -> Result<T, HeapError> {
+        let idx = self.locate(handle)?;
+        let old = std::mem::replace(&mut self.data[idx], element);
+        self.restore(idx);
+        Ok(old)
+    }
+
+    /// Takes the element behind `handle` out of the heap.
+    pub fn remove(&mut self, handle: Handle) -> Result<T, HeapError> {
+        let idx = self.locate(handle)?;
+        let last = self.data.len() - 1;
+
+        self.swap(idx, last);
+        let element = self.pop_last().unwrap();
+        if idx < last {
+            self.restore(idx);
+        }
+        Ok(element)
+    }
 
-        while current > 0 && self.data[self.parent(current)] > self.data[current] {
-            let parent = self.parent(current);
-            self.data.swap(current, parent);
-            current = self.parent(current);
+    /// Moves the element at `idx` up or down, whichever its new value needs.
+    fn restore(&mut self, idx: usize) {
+        if idx > 0 && self.before(idx, self.parent(idx)) {
+            self.sift_up(idx);
+        } else {
+            self.heapify(idx);
         }
     }
 
     pub fn pop(&mut self) -> Option<T> {
         match self.data.len() {
-            n if n == 0 => None,
-            n if n == 1 => self.data.pop(),
+            0 => None,
+            1 => self.pop_last(),
             _ => {
                 let last_element_idx = self.data.len() - 1;
-                self.data.swap(0, last_element_idx);
-                let value = self.data.pop().unwrap();
+                self.swap(0, last_element_idx);
+                let value = self.pop_last().unwrap();
 
                 self.heapify(0);
 
@@ -53,12 +247,16 @@ impl<T: Ord + Copy> Heap<T> {
         }
     }
 
+    fn swap(&mut self, a: usize, b: usize) {
+        self.data.swap(a, b);
+        self.owners.swap(a, b);
+        self.stamps.swap(a, b);
+        self.slots.relocate(self.owners[a], a);
+        self.slots.relocate(self.owners[b], b);
+    }
+
     fn parent(&self, idx: usize) -> usize {
-        if idx % 2 == 0 {
-            (idx - 1) / 2
-        } else {
-            idx / 2
-        }
+        (idx - 1) / 2
     }
 
     fn left(&self, idx: usize) -> usize {
@@ -69,30 +267,143 @@ impl<T: Ord + Copy> Heap<T> {
         2 * idx + 2
     }
 
-    pub fn heapify(&mut self, idx: usize) {
+    fn heapify(&mut self, idx: usize) {
         let left_idx = self.left(idx);
         let right_idx = self.right(idx);
         let size = self.data.len();
         let mut min_idx = idx;
 
-        if left_idx < size && self.data[left_idx] < self.data[min_idx] {
+        if left_idx < size && self.before(left_idx, min_idx) {
             min_idx = left_idx;
         };
 
-        if right_idx < size && self.data[right_idx] < self.data[min_idx] {
+        if right_idx < size && self.before(right_idx, min_idx) {
             min_idx = right_idx;
         }
 
         if min_idx != idx {
-            self.data.swap(idx, min_idx);
+            self.swap(idx, min_idx);
             self.heapify(min_idx);
         }
     }
 }
 
+impl<T: std::fmt::Debug, C: Compare<T>> Heap<T, C> {
+    /// Panics unless every element comes out no earlier than its parent and every live
+    /// handle points at its element. Walks the whole heap, so it is meant for tests and
+    /// for `debug_assert`-style checks while debugging.
+    #[doc(hidden)]
+    pub fn check_invariant(&self) {
+        let size = self.data.len();
+        assert_eq!(self.owners.len(), size, "one owner per element");
+        assert_eq!(self.stamps.len(), size, "one stamp per element");
+
+        (1..size).for_each(|idx| {
+            let parent_idx = self.parent(idx);
+            assert!(
+                !self.before(idx, parent_idx),
+                "{:?} at {} comes out before its parent {:?} at {}",
+                self.data[idx],
+                idx,
+                self.data[parent_idx],
+                parent_idx
+            );
+        });
+
+        self.owners.iter().enumerate().for_each(|(idx, slot)| {
+            assert_eq!(
+                self.slots.location(*slot),
+                Some(idx),
+                "handle slot {} of {:?} points elsewhere",
+                slot,
+                self.data[idx]
+            );
+        });
+    }
+}
+
+impl<T, C: Compare<T>> PriorityQueue<T> for Heap<T, C> {
+    type Handle = Handle;
+
+    fn push(&mut self, element: T) -> Handle {
+        self.insert(element)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        Heap::pop(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        Heap::peek(self)
+    }
+
+    fn get(&self, handle: Handle) -> Option<&T> {
+        Heap::get(self, handle)
+    }
+
+    fn decrease_key(&mut self, handle: Handle, element: T) -> Result<(), HeapError> {
+        Heap::decrease_key(self, handle, element)
+    }
+
+    fn len(&self) -> usize {
+        Heap::len(self)
+    }
+}
+
+impl<T, C: Compare<T> + Default> Default for Heap<T, C> {
+    fn default() -> Self {
+        Heap::with_comparator(C::default())
+    }
+}
+
+impl<T, C: Compare<T> + Default> FromIterator<T> for Heap<T, C> {
+    fn from_iter<I: IntoIterator<Item = T>>(elements: I) -> Self {
+        Heap::from_vec_with_comparator(Vec::from_iter(elements), C::default())
+    }
+}
+
+impl<T, C: Compare<T>> Extend<T> for Heap<T, C> {
+    /// Rebuilds the heap in one pass when adding more elements than it holds, and
+    /// inserts them one by one otherwise.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, elements: I) {
+        let elements = Vec::from_iter(elements);
+        if elements.len() > self.len() {
+            self.append_unordered(elements);
+        } else {
+            elements.into_iter().for_each(|element| {
+                self.insert(element);
+            });
+        }
+    }
+}
+
+/// Sorts `items` ascending with a binary heap.
+pub fn heapsort<T: Ord>(items: Vec<T>) -> Vec<T> {
+    Heap::from_vec(items).into_ordered_vec()
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Heap;
+    use super::{
+        dary::DaryHeap,
+        dial::DialQueue,
+        fibonacci::FibonacciHeap,
+        heapsort,
+        pairing::PairingHeap,
+        queue::{HeapError, PriorityQueue},
+        radix::{RadixHeap, RadixKey},
+        Heap, MaxHeap,
+    };
+    use proptest::{collection::vec, prelude::*};
+    use std::{cmp::Reverse, collections::BinaryHeap};
+
+    fn sequence(length: usize, seed: i64) -> Vec<i64> {
+        let mut value = seed;
+        Vec::from_iter((0..length).map(|_| {
+            value = (value * 1103 + 12345) % 10007;
+            value % 500
+        }))
+    }
 
     #[test]
     fn insert() {
@@ -106,10 +417,489 @@ mod tests {
         assert_eq!(heap.pop().unwrap(), 3);
     }
 
+    #[test]
+    fn orderings() {
+        let mut heap = MaxHeap::new_max();
+        [3, 1, 4, 1, 5].into_iter().for_each(|value| {
+            heap.insert(value);
+        });
+        assert_eq!(
+            Vec::from_iter(std::iter::from_fn(|| heap.pop())),
+            [5, 4, 3, 1, 1]
+        );
+
+        let mut heap = Heap::by_key(|name: &String| name.len());
+        ["Klaipeda", "Vilnius", "Kaunas"]
+            .into_iter()
+            .for_each(|name| {
+                heap.insert(name.to_owned());
+            });
+        let handle = heap.push("Panevezys".to_owned());
+        assert_eq!(heap.decrease_key(handle, "Riga".to_owned()), Ok(()));
+        assert_eq!(heap.pop().as_deref(), Some("Riga"));
+        assert_eq!(heap.pop().as_deref(), Some("Kaunas"));
+
+        let mut heap = Heap::with_comparator(|a: &(u32, char), b: &(u32, char)| {
+            b.0.cmp(&a.0).then(a.1.cmp(&b.1))
+        });
+        [(1, 'b'), (2, 'z'), (1, 'a'), (2, 'y')]
+            .into_iter()
+            .for_each(|pair| {
+                heap.insert(pair);
+            });
+        assert_eq!(
+            Vec::from_iter(std::iter::from_fn(|| heap.pop())),
+            [(2, 'y'), (2, 'z'), (1, 'a'), (1, 'b')]
+        );
+    }
+
+    #[test]
+    fn construction_matches_binary_heap() {
+        let data = sequence(300, 3);
+
+        let heap = Heap::from_vec(data.clone());
+        assert_eq!(heap.len(), 300);
+        assert_eq!(heap.peek(), data.iter().min());
+        assert_eq!(
+            heap.into_ordered_vec(),
+            BinaryHeap::from(data.clone()).into_sorted_vec()
+        );
+
+        let mut heap: Heap<i64> = data.iter().copied().collect();
+        let mut expected = BinaryHeap::from_iter(data.iter().copied().map(Reverse));
+        heap.extend(sequence(10, 5));
+        expected.extend(sequence(10, 5).into_iter().map(Reverse));
+        heap.extend(sequence(1000, 7));
+        expected.extend(sequence(1000, 7).into_iter().map(Reverse));
+
+        assert_eq!(heap.len(), expected.len());
+        while let Some(Reverse(value)) = expected.pop() {
+            assert_eq!(heap.pop(), Some(value));
+        }
+        assert!(heap.is_empty());
+        assert_eq!(heap.peek(), None);
+    }
+
+    #[test]
+    fn merge_and_pop_iter() {
+        let mut heap = MaxHeap::from_vec_with_comparator(sequence(50, 11), Default::default());
+        let handle = heap.push(1000);
+        heap.merge(sequence(70, 13).into_iter().collect());
+        assert_eq!(heap.get(handle), Some(&1000));
+
+        let mut expected = BinaryHeap::from([sequence(50, 11), sequence(70, 13)].concat());
+        expected.push(1000);
+        assert_eq!(heap.peek(), expected.peek());
+
+        let popped = Vec::from_iter(heap.pop_iter().take(20));
+        assert_eq!(
+            popped,
+            Vec::from_iter((0..20).map(|_| expected.pop().unwrap()))
+        );
+        assert_eq!(heap.len(), expected.len());
+
+        let mut rest = heap.into_ordered_vec();
+        rest.reverse();
+        assert_eq!(rest, expected.into_sorted_vec());
+    }
+
+    #[test]
+    fn heapsort_() {
+        let data = sequence(257, 17);
+        let mut sorted = data.clone();
+        sorted.sort();
+        assert_eq!(heapsort(data), sorted);
+        assert!(heapsort(Vec::<i64>::new()).is_empty());
+    }
+
+    #[test]
+    fn handles() {
+        let mut heap = Heap::new();
+        let handles = Vec::from_iter(
+            sequence(100, 19)
+                .into_iter()
+                .map(|value| heap.insert(value)),
+        );
+        let mut expected = sequence(100, 19);
+
+        assert_eq!(
+            heap.decrease_key(handles[0], expected[0] + 1),
+            Err(HeapError::KeyIncreased)
+        );
+        assert_eq!(
+            heap.increase_key(handles[0], expected[0] - 1),
+            Err(HeapError::KeyDecreased)
+        );
+
+        (0..100).step_by(4).for_each(|idx| {
+            expected[idx] += 1000;
+            assert_eq!(heap.increase_key(handles[idx], expected[idx]), Ok(()));
+        });
+        (1..100).step_by(4).for_each(|idx| {
+            expected[idx] -= 1000;
+            assert_eq!(heap.decrease_key(handles[idx], expected[idx]), Ok(()));
+        });
+        (2..100).step_by(4).for_each(|idx| {
+            let value = (idx as i64 * 37) % 700 - 100;
+            assert_eq!(heap.update(handles[idx], value), Ok(expected[idx]));
+            expected[idx] = value;
+        });
+        (3..100).step_by(8).for_each(|idx| {
+            assert_eq!(heap.remove(handles[idx]), Ok(expected[idx]));
+            assert_eq!(heap.remove(handles[idx]), Err(HeapError::InvalidHandle));
+            assert_eq!(heap.get(handles[idx]), None);
+        });
+        expected = Vec::from_iter(
+            expected
+                .into_iter()
+                .enumerate()
+                .filter(|(idx, _)| idx % 8 != 3)
+                .map(|(_, value)| value),
+        );
+
+        assert_eq!(heap.get(handles[2]), Some(&-26));
+        expected.sort();
+        assert_eq!(heap.into_ordered_vec(), expected);
+    }
+
+    #[test]
+    fn equal_keys_come_out_first_in_first_out() {
+        fn priority(pair: &(u32, char)) -> u32 {
+            pair.0
+        }
+
+        let mut heap = Heap::by_key(priority);
+        let handle = heap.insert((5, 'a'));
+        [(1, 'b'), (3, 'c'), (1, 'd'), (3, 'e'), (1, 'f')]
+            .into_iter()
+            .for_each(|pair| {
+                heap.insert(pair);
+            });
+        assert_eq!(heap.decrease_key(handle, (1, 'a')), Ok(()));
+
+        let mut other = Heap::by_key(priority);
+        other.extend([(3, 'g'), (1, 'h'), (3, 'i')]);
+        heap.merge(other);
+
+        assert_eq!(
+            String::from_iter(heap.pop_iter().map(|(_, name)| name)),
+            "abdfhcegi"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "comes out before its parent")]
+    fn invariant_violation() {
+        let mut heap = Heap::from_vec(sequence(20, 23));
+        heap.check_invariant();
+        heap.data.swap(0, 19);
+        heap.check_invariant();
+    }
+
+    #[derive(Clone, Debug)]
+    enum Op {
+        Insert(u8),
+        Pop,
+        DecreaseKey(usize, u8),
+        IncreaseKey(usize, u8),
+        Update(usize, u8),
+        Remove(usize),
+    }
+
+    fn op() -> impl Strategy<Value = Op> {
+        // Few distinct keys, so that most operations involve ties.
+        let key = 0u8..8;
+        prop_oneof![
+            4 => key.clone().prop_map(Op::Insert),
+            2 => Just(Op::Pop),
+            1 => (any::<usize>(), key.clone()).prop_map(|(idx, key)| Op::DecreaseKey(idx, key)),
+            1 => (any::<usize>(), key.clone()).prop_map(|(idx, key)| Op::IncreaseKey(idx, key)),
+            1 => (any::<usize>(), key).prop_map(|(idx, key)| Op::Update(idx, key)),
+            1 => any::<usize>().prop_map(Op::Remove),
+        ]
+    }
+
+    fn key(element: &(u8, usize)) -> u8 {
+        element.0
+    }
+
+    /// Key of every element ever inserted, `None` once it has left, in insertion order
+    /// like the handles. The next element out is the first live one with the smallest key.
+    struct Model(Vec<Option<u8>>);
+
+    impl Model {
+        fn first(&self) -> Option<(u8, usize)> {
+            self.0
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, entry)| entry.map(|key| (key, idx)))
+                .min()
+        }
+
+        fn len(&self) -> usize {
+            self.0.iter().flatten().count()
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn heap_matches_model(ops in vec(op(), 0..300)) {
+            let mut heap = Heap::by_key(key);
+            let mut handles = Vec::new();
+            let mut model = Model(Vec::new());
+
+            for op in ops {
+                let target = |idx: usize| idx % handles.len().max(1);
+                match op {
+                    Op::Insert(key) => {
+                        handles.push(heap.insert((key, model.0.len())));
+                        model.0.push(Some(key));
+                    }
+                    Op::Pop => {
+                        let expected = model.first();
+                        if let Some((_, idx)) = expected {
+                            model.0[idx] = None;
+                        }
+                        prop_assert_eq!(heap.pop(), expected);
+                    }
+                    Op::DecreaseKey(idx, key) if !handles.is_empty() => {
+                        let idx = target(idx);
+                        let expected = match &mut model.0[idx] {
+                            None => Err(HeapError::InvalidHandle),
+                            Some(old) if key > *old => Err(HeapError::KeyIncreased),
+                            Some(old) => {
+                                *old = key;
+                                Ok(())
+                            }
+                        };
+                        prop_assert_eq!(heap.decrease_key(handles[idx], (key, idx)), expected);
+                    }
+                    Op::IncreaseKey(idx, key) if !handles.is_empty() => {
+                        let idx = target(idx);
+                        let expected = match &mut model.0[idx] {
+                            None => Err(HeapError::InvalidHandle),
+                            Some(old) if key < *old => Err(HeapError::KeyDecreased),
+                            Some(old) => {
+                                *old = key;
+                                Ok(())
+                            }
+                        };
+                        prop_assert_eq!(heap.increase_key(handles[idx], (key, idx)), expected);
+                    }
+                    Op::Update(idx, key) if !handles.is_empty() => {
+                        let idx = target(idx);
+                        let expected = match &mut model.0[idx] {
+                            None => Err(HeapError::InvalidHandle),
+                            Some(old) => Ok((std::mem::replace(old, key), idx)),
+                        };
+                        prop_assert_eq!(heap.update(handles[idx], (key, idx)), expected);
+                    }
+                    Op::Remove(idx) if !handles.is_empty() => {
+                        let idx = target(idx);
+                        let expected = model.0[idx]
+                            .take()
+                            .map(|key| (key, idx))
+                            .ok_or(HeapError::InvalidHandle);
+                        prop_assert_eq!(heap.remove(handles[idx]), expected);
+                    }
+                    _ => {}
+                }
+
+                heap.check_invariant();
+                prop_assert_eq!(heap.len(), model.len());
+                prop_assert_eq!(heap.peek().copied(), model.first());
+            }
+
+            while let Some(expected) = model.first() {
+                model.0[expected.1] = None;
+                prop_assert_eq!(heap.pop(), Some(expected));
+            }
+            prop_assert!(heap.is_empty());
+        }
+
+        #[test]
+        fn bulk_construction_is_stable(
+            first in vec(0u8..8, 0..200),
+            second in vec(0u8..8, 0..200),
+        ) {
+            let mut heap = Heap::by_key(key);
+            heap.extend(first.iter().copied().enumerate().map(|(idx, key)| (key, idx)));
+            heap.check_invariant();
+            heap.extend(
+                second
+                    .iter()
+                    .copied()
+                    .enumerate()
+                    .map(|(idx, key)| (key, first.len() + idx)),
+            );
+            heap.check_invariant();
+
+            let mut expected = Vec::from_iter(
+                [first, second]
+                    .concat()
+                    .into_iter()
+                    .enumerate()
+                    .map(|(idx, key)| (key, idx)),
+            );
+            expected.sort_by_key(key);
+            prop_assert_eq!(heap.into_ordered_vec(), expected);
+        }
+    }
+
     #[test]
     fn parent() {
         let heap = Heap::<i64>::new();
         assert_eq!(heap.right(1), 4);
         assert_eq!(heap.parent(4), 1);
     }
+
+    /// Pushes a fixed pseudo-random sequence, lowers part of it, refills the queue half
+    /// way through and checks that everything comes out in order.
+    fn exercise<Q: PriorityQueue<i64>>(mut queue: Q) {
+        let mut expected = Vec::new();
+        let mut handles = Vec::new();
+        let mut value: i64 = 7;
+        (0..200).for_each(|_| {
+            value = (value * 31 + 11) % 1009;
+            handles.push(queue.push(value));
+            expected.push(value);
+        });
+        assert_eq!(queue.len(), 200);
+        assert_eq!(queue.peek(), expected.iter().min());
+
+        (0..200).step_by(3).for_each(|idx| {
+            assert_eq!(queue.get(handles[idx]), Some(&expected[idx]));
+            expected[idx] -= 500;
+            assert_eq!(queue.decrease_key(handles[idx], expected[idx]), Ok(()));
+        });
+        assert_eq!(
+            queue.decrease_key(handles[1], expected[1] + 1),
+            Err(HeapError::KeyIncreased)
+        );
+
+        let first = Vec::from_iter((0..100).map(|_| queue.pop().unwrap()));
+        (0..100).for_each(|value| {
+            queue.push(value * 3);
+            expected.push(value * 3);
+        });
+        let second = Vec::from_iter(std::iter::from_fn(|| queue.pop()));
+
+        assert!(first.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!(second.windows(2).all(|pair| pair[0] <= pair[1]));
+        let mut popped = [first, second].concat();
+        popped.sort();
+        expected.sort();
+        assert_eq!(popped, expected);
+
+        assert!(queue.is_empty());
+        assert_eq!(queue.peek(), None);
+        assert_eq!(queue.get(handles[0]), None);
+        assert_eq!(
+            queue.decrease_key(handles[0], 0),
+            Err(HeapError::InvalidHandle)
+        );
+    }
+
+    #[test]
+    fn queues() {
+        exercise(Heap::new());
+        exercise(DaryHeap::<i64, 4>::new());
+        exercise(PairingHeap::new());
+        exercise(FibonacciHeap::new());
+    }
+
+    /// Pops in order while pushing and lowering keys no further than 50 above the last
+    /// popped one, the way Dijkstra's algorithm uses a monotone queue.
+    fn exercise_monotone<Q: PriorityQueue<u64>>(mut queue: Q) {
+        let mut popped = Vec::new();
+        let mut handles = Vec::new();
+        let mut value: u64 = 7;
+        queue.push(0);
+
+        while let Some(last) = queue.pop() {
+            popped.push(last);
+            if popped.len() > 300 {
+                continue;
+            }
+            (0..3).for_each(|_| {
+                value = (value * 31 + 11) % 1009;
+                handles.push(queue.push(last + value % 50 + 1));
+            });
+            let handle = handles[value as usize % handles.len()];
+            if let Some(current) = queue.get(handle).copied() {
+                assert_eq!(
+                    queue.decrease_key(handle, current + 1),
+                    Err(HeapError::KeyIncreased)
+                );
+                assert_eq!(queue.decrease_key(handle, last.max(current / 2)), Ok(()));
+            }
+        }
+
+        assert!(popped.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert_eq!(popped.len(), 1 + 3 * 300);
+        assert_eq!(
+            queue.decrease_key(handles[0], 0),
+            Err(HeapError::InvalidHandle)
+        );
+    }
+
+    /// A key shared by many elements that `Ord` still tells apart.
+    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+    struct Tied(u64, u32);
+
+    impl RadixKey for Tied {
+        fn radix_key(&self) -> u64 {
+            self.0
+        }
+    }
+
+    /// Pushes a few hundred elements onto the same key, out of `Ord` order.
+    fn exercise_equal_keys<Q: PriorityQueue<Tied>>(mut queue: Q) {
+        let handles = Vec::from_iter((0..500).map(|index| queue.push(Tied(3, index * 7 % 500))));
+        queue.push(Tied(9, 0));
+        assert_eq!(
+            queue.decrease_key(handles[10], Tied(3, 1000)),
+            Err(HeapError::KeyIncreased)
+        );
+        assert_eq!(queue.pop(), Some(Tied(3, 0)));
+        assert_eq!(queue.decrease_key(handles[20], Tied(3, 0)), Ok(()));
+
+        let popped = Vec::from_iter(std::iter::from_fn(|| queue.pop()));
+        assert_eq!(popped.len(), 500);
+        assert!(popped.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert_eq!(popped.last(), Some(&Tied(9, 0)));
+    }
+
+    #[test]
+    fn monotone_queues() {
+        exercise_monotone(RadixHeap::new());
+        exercise_monotone(DialQueue::new(50));
+        exercise_equal_keys(RadixHeap::new());
+        exercise_equal_keys(DialQueue::new(10));
+
+        let mut queue = RadixHeap::new();
+        let handle = queue.push(10u64);
+        queue.push(4);
+        assert_eq!(queue.peek(), Some(&4));
+        assert_eq!(queue.pop(), Some(4));
+        assert_eq!(queue.decrease_key(handle, 3), Err(HeapError::NotMonotone));
+        assert_eq!(queue.try_push(3), Err(HeapError::NotMonotone));
+        assert!(queue.try_push(4).is_ok());
+        assert_eq!(queue.len(), 2);
+
+        let mut queue = DialQueue::new(5);
+        queue.push(2u64);
+        assert_eq!(queue.pop(), Some(2));
+        assert_eq!(queue.try_push(1), Err(HeapError::NotMonotone));
+        assert_eq!(queue.try_push(8), Err(HeapError::NotMonotone));
+        let handle = queue.try_push(7).unwrap();
+        assert_eq!(queue.get(handle), Some(&7));
+        assert_eq!(queue.len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "needs more buckets than fit in memory")]
+    fn dial_span_overflow() {
+        DialQueue::<u64>::new(u64::MAX);
+    }
 }
@@ -0,0 +1,170 @@
+use super::queue::{Handle, HeapError, PriorityQueue, Slots};
+
+/// Min-heap kept as a single multiway tree. Pushing and lowering a key are `O(1)`;
+/// `pop` pairs up the root's children, amortized `O(log n)`.
+pub struct PairingHeap<T> {
+    nodes: Vec<Option<PairingNode<T>>>,
+    free: Vec<usize>,
+    root: Option<usize>,
+    len: usize,
+    slots: Slots,
+}
+
+struct PairingNode<T> {
+    element: T,
+    child: Option<usize>,
+    sibling: Option<usize>,
+    /// Parent for a first child, previous sibling otherwise.
+    prev: Option<usize>,
+    slot: u32,
+}
+
+impl<T: Ord> PairingHeap<T> {
+    pub fn new() -> Self {
+        PairingHeap {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            root: None,
+            len: 0,
+            slots: Slots::default(),
+        }
+    }
+
+    fn node(&self, idx: usize) -> &PairingNode<T> {
+        self.nodes[idx].as_ref().unwrap()
+    }
+
+    fn node_mut(&mut self, idx: usize) -> &mut PairingNode<T> {
+        self.nodes[idx].as_mut().unwrap()
+    }
+
+    /// Joins two detached trees and returns the root of the result.
+    fn meld(&mut self, a: usize, b: usize) -> usize {
+        let (parent, child) = match self.node(b).element < self.node(a).element {
+            true => (b, a),
+            false => (a, b),
+        };
+
+        let first = self.node(parent).child;
+        if let Some(first) = first {
+            self.node_mut(first).prev = Some(child);
+        }
+        let node = self.node_mut(child);
+        node.sibling = first;
+        node.prev = Some(parent);
+        self.node_mut(parent).child = Some(child);
+        parent
+    }
+
+    /// Detaches the subtree at `idx` from its parent and siblings.
+    fn cut(&mut self, idx: usize) {
+        let node = self.node_mut(idx);
+        let (prev, sibling) = (node.prev.take(), node.sibling.take());
+
+        if let Some(prev) = prev {
+            let prev_node = self.node_mut(prev);
+            if prev_node.child == Some(idx) {
+                prev_node.child = sibling;
+            } else {
+                prev_node.sibling = sibling;
+            }
+        }
+        if let Some(sibling) = sibling {
+            self.node_mut(sibling).prev = prev;
+        }
+    }
+
+    /// Melds the children of a removed root in pairs from the left, then folds the pairs
+    /// together from the right.
+    fn merge_children(&mut self, first: Option<usize>) -> Option<usize> {
+        let mut children = Vec::new();
+        let mut current = first;
+        while let Some(child) = current {
+            let node = self.node_mut(child);
+            current = node.sibling.take();
+            node.prev = None;
+            children.push(child);
+        }
+
+        let pairs = Vec::from_iter(children.chunks(2).map(|pair| match pair {
+            [a, b] => self.meld(*a, *b),
+            [a] => *a,
+            _ => unreachable!(),
+        }));
+        pairs
+            .into_iter()
+            .rev()
+            .reduce(|right, left| self.meld(left, right))
+    }
+}
+
+impl<T: Ord> PriorityQueue<T> for PairingHeap<T> {
+    type Handle = Handle;
+
+    fn push(&mut self, element: T) -> Handle {
+        let idx = self.free.pop().unwrap_or(self.nodes.len());
+        let handle = self.slots.insert(idx);
+        let node = PairingNode {
+            element,
+            child: None,
+            sibling: None,
+            prev: None,
+            slot: handle.slot(),
+        };
+        match idx == self.nodes.len() {
+            true => self.nodes.push(Some(node)),
+            false => self.nodes[idx] = Some(node),
+        }
+
+        self.root = Some(match self.root {
+            Some(root) => self.meld(root, idx),
+            None => idx,
+        });
+        self.len += 1;
+        handle
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        let root = self.root?;
+        let node = self.nodes[root].take().unwrap();
+        self.free.push(root);
+        self.slots.remove(node.slot);
+        self.len -= 1;
+
+        self.root = self.merge_children(node.child);
+        Some(node.element)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.root.map(|root| &self.node(root).element)
+    }
+
+    fn get(&self, handle: Handle) -> Option<&T> {
+        self.slots.get(handle).map(|idx| &self.node(idx).element)
+    }
+
+    fn decrease_key(&mut self, handle: Handle, element: T) -> Result<(), HeapError> {
+        let idx = self.slots.get(handle).ok_or(HeapError::InvalidHandle)?;
+        if element > self.node(idx).element {
+            return Err(HeapError::KeyIncreased);
+        }
+
+        self.node_mut(idx).element = element;
+        let root = self.root.unwrap();
+        if idx != root {
+            self.cut(idx);
+            self.root = Some(self.meld(root, idx));
+        }
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<T: Ord> Default for PairingHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
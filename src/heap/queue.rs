@@ -0,0 +1,150 @@
+use std::fmt::Debug;
+
+/// A min-priority queue whose elements can be lowered in place through the handle
+/// returned when they were pushed. "Smaller" follows the queue's own ordering, which
+/// is `Ord` unless the queue takes a comparator.
+pub trait PriorityQueue<T> {
+    type Handle: Copy + Eq + Debug;
+
+    fn push(&mut self, element: T) -> Self::Handle;
+
+    /// Removes and returns the smallest element. Its handle becomes invalid.
+    fn pop(&mut self) -> Option<T>;
+
+    fn peek(&self) -> Option<&T>;
+
+    /// The element behind `handle`, or `None` once it has been popped.
+    fn get(&self, handle: Self::Handle) -> Option<&T>;
+
+    /// Replaces the element behind `handle` with a smaller or equal one.
+    fn decrease_key(&mut self, handle: Self::Handle, element: T) -> Result<(), HeapError>;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum HeapError {
+    /// The handle's element has already left the queue.
+    InvalidHandle,
+    /// `decrease_key` was given an element bigger than the current one.
+    KeyIncreased,
+    /// `increase_key` was given an element smaller than the current one.
+    KeyDecreased,
+    /// A monotone queue was given a key below the last one it popped, or, for a
+    /// [`DialQueue`](super::dial::DialQueue), more than its span above it.
+    NotMonotone,
+}
+
+/// Refers to one element pushed into a queue. Only meaningful for the queue that
+/// returned it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Handle {
+    slot: u32,
+    generation: u32,
+}
+
+impl Handle {
+    pub(crate) fn slot(self) -> u32 {
+        self.slot
+    }
+}
+
+/// Maps handles to where their element currently lives in a queue. A slot is reused
+/// once its element leaves the queue, with a new generation so old handles stay invalid.
+#[derive(Debug, Clone)]
+pub(crate) struct Slots<L = usize> {
+    entries: Vec<Entry<L>>,
+    free: Vec<u32>,
+}
+
+#[derive(Debug, Clone)]
+struct Entry<L> {
+    generation: u32,
+    location: Option<L>,
+}
+
+impl<L> Default for Slots<L> {
+    fn default() -> Self {
+        Slots {
+            entries: Vec::new(),
+            free: Vec::new(),
+        }
+    }
+}
+
+impl<L: Copy> Slots<L> {
+    pub(crate) fn insert(&mut self, location: L) -> Handle {
+        let slot = match self.free.pop() {
+            Some(slot) => slot,
+            None => {
+                self.entries.push(Entry {
+                    generation: 0,
+                    location: None,
+                });
+                (self.entries.len() - 1) as u32
+            }
+        };
+
+        let entry = &mut self.entries[slot as usize];
+        entry.location = Some(location);
+        Handle {
+            slot,
+            generation: entry.generation,
+        }
+    }
+
+    pub(crate) fn get(&self, handle: Handle) -> Option<L> {
+        self.entries
+            .get(handle.slot as usize)
+            .filter(|entry| entry.generation == handle.generation)
+            .and_then(|entry| entry.location)
+    }
+
+    pub(crate) fn reserve(&mut self, additional: usize) {
+        self.entries.reserve(additional);
+        self.free.reserve(additional);
+    }
+
+    /// Where the element in `slot` lives, or `None` while the slot is free.
+    pub(crate) fn location(&self, slot: u32) -> Option<L> {
+        self.entries
+            .get(slot as usize)
+            .and_then(|entry| entry.location)
+    }
+
+    pub(crate) fn relocate(&mut self, slot: u32, location: L) {
+        self.entries[slot as usize].location = Some(location);
+    }
+
+    pub(crate) fn remove(&mut self, slot: u32) {
+        let entry = &mut self.entries[slot as usize];
+        entry.location = None;
+        entry.generation = entry.generation.wrapping_add(1);
+        self.free.push(slot);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Slots;
+
+    #[test]
+    fn stale_handles() {
+        let mut slots = Slots::<usize>::default();
+        let first = slots.insert(3);
+        assert_eq!(slots.get(first), Some(3));
+
+        slots.relocate(first.slot(), 5);
+        assert_eq!(slots.get(first), Some(5));
+
+        slots.remove(first.slot());
+        let second = slots.insert(7);
+        assert_eq!(second.slot(), first.slot());
+        assert_eq!(slots.get(first), None);
+        assert_eq!(slots.get(second), Some(7));
+    }
+}
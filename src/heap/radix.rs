@@ -0,0 +1,134 @@
+use super::{
+    bucket::Buckets,
+    queue::{Handle, HeapError, PriorityQueue},
+};
+
+/// Elements that carry an unsigned integer priority, consistent with their `Ord`.
+pub trait RadixKey {
+    fn radix_key(&self) -> u64;
+}
+
+macro_rules! impl_radix_key {
+    ($($t:ty),*) => {
+        $(impl RadixKey for $t {
+            fn radix_key(&self) -> u64 {
+                *self as u64
+            }
+        })*
+    };
+}
+
+impl_radix_key!(u8, u16, u32, u64, usize);
+
+/// Monotone min-queue for integer keys: no element may be pushed below the last popped
+/// one, which always holds in Dijkstra's algorithm with non-negative weights.
+///
+/// Elements are grouped by the highest bit in which their key differs from the last
+/// popped key, so every element moves between buckets at most 64 times. Buckets are
+/// small binary heaps, so elements sharing the last popped key pop in `O(log n)` each.
+pub struct RadixHeap<T> {
+    buckets: Buckets<T>,
+    last: u64,
+}
+
+const RADIX_BUCKETS: usize = u64::BITS as usize + 1;
+
+impl<T: Ord + RadixKey> RadixHeap<T> {
+    pub fn new() -> Self {
+        RadixHeap {
+            buckets: Buckets::new(RADIX_BUCKETS),
+            last: 0,
+        }
+    }
+
+    /// Like [`PriorityQueue::push`], but returns [`HeapError::NotMonotone`] instead of
+    /// panicking when the key is below the last popped one.
+    pub fn try_push(&mut self, element: T) -> Result<Handle, HeapError> {
+        match element.radix_key() >= self.last {
+            true => Ok(self
+                .buckets
+                .insert(self.bucket(element.radix_key()), element)),
+            false => Err(HeapError::NotMonotone),
+        }
+    }
+
+    fn bucket(&self, key: u64) -> usize {
+        (u64::BITS - (key ^ self.last).leading_zeros()) as usize
+    }
+
+    fn first_bucket(&self) -> Option<usize> {
+        (0..RADIX_BUCKETS).find(|bucket| !self.buckets.list(*bucket).is_empty())
+    }
+}
+
+impl<T: Ord + RadixKey> PriorityQueue<T> for RadixHeap<T> {
+    type Handle = Handle;
+
+    /// # Panics
+    ///
+    /// If the key of `element` is below the last popped key; [`RadixHeap::try_push`]
+    /// reports that instead.
+    fn push(&mut self, element: T) -> Handle {
+        let key = element.radix_key();
+        assert!(
+            key >= self.last,
+            "key {key} below last popped key {}",
+            self.last
+        );
+        self.buckets.insert(self.bucket(key), element)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        let bucket = self.first_bucket()?;
+
+        if bucket != 0 {
+            self.last = self.buckets.first(bucket).unwrap().radix_key();
+
+            self.buckets
+                .drain(bucket)
+                .into_iter()
+                .for_each(|(element, slot)| {
+                    let target = self.bucket(element.radix_key());
+                    self.buckets.reinsert(target, element, slot);
+                });
+        }
+
+        self.buckets.remove_first(0)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.buckets.first(self.first_bucket()?)
+    }
+
+    fn get(&self, handle: Handle) -> Option<&T> {
+        self.buckets.get(handle)
+    }
+
+    fn decrease_key(&mut self, handle: Handle, element: T) -> Result<(), HeapError> {
+        let (bucket, position) = self
+            .buckets
+            .locate(handle)
+            .ok_or(HeapError::InvalidHandle)?;
+        if element > self.buckets.list(bucket)[position].0 {
+            return Err(HeapError::KeyIncreased);
+        }
+        if element.radix_key() < self.last {
+            return Err(HeapError::NotMonotone);
+        }
+
+        let (_, slot) = self.buckets.take(bucket, position);
+        let target = self.bucket(element.radix_key());
+        self.buckets.reinsert(target, element, slot);
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.buckets.len()
+    }
+}
+
+impl<T: Ord + RadixKey> Default for RadixHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
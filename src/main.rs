@@ -1,12 +1,8 @@
-pub mod graph;
-pub mod heap;
-use graph::generate::generate_undirected;
-use graph::generate::GenerationParameters;
-
-use crate::graph::djikstra::djikstra;
-use crate::graph::print::print_to_file;
-use crate::graph::read::read_from_file;
-use crate::graph::Graph;
+use rusty_graph::graph::generate::generate_undirected;
+use rusty_graph::graph::generate::GenerationParameters;
+
+use rusty_graph::graph::djikstra::djikstra;
+use rusty_graph::graph::print::print_to_file;
 fn main() {
     let parameters = GenerationParameters {
         vertex_count: 1000,
@@ -22,13 +18,15 @@ fn main() {
     print!("Printing graph!");
     print_to_file(&graph, "files/big.graph").expect("Couldn't write file!");
 
+    let frozen = graph.freeze();
+
     let mut min = None;
     let mut min_vertex = None;
 
-    graph.get_vertices().iter().for_each(|vertex| {
-        println!("Calculating for vertex {}!", **vertex);
+    graph.vertices().for_each(|vertex| {
+        println!("Calculating for vertex {}!", *vertex);
 
-        let result = djikstra(&graph, **vertex);
+        let result = djikstra(&frozen, *vertex);
         let mut sum = 0;
         let mut len = 0;
         result.iter().for_each(|node| {
@@ -41,7 +39,7 @@ fn main() {
 
         if min.is_none() || min > Some(avg) {
             min = Some(avg);
-            min_vertex = Some(**vertex);
+            min_vertex = Some(*vertex);
         }
 
         //dbg!(result, avg, **vertex, min, min_vertex);